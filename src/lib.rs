@@ -63,18 +63,48 @@
 //! ```
 
 use std::fs::File;
-use std::io::{Read, Result, Write};
+use std::io::{BufWriter, Error, ErrorKind, Read, Result, Write};
 use std::path::Path;
 
+#[cfg(test)]
+macro_rules! assert_close(
+    ($one:expr, $two:expr) => (assert_close!($one, $two, 1e-4));
+    ($one:expr, $two:expr, $tolerance:expr) => ({
+        let (one, two): ((f32, f32), (f32, f32)) = ($one, $two);
+        let tolerance: f32 = $tolerance;
+        assert!(
+            (one.0 - two.0).abs() < tolerance && (one.1 - two.1).abs() < tolerance,
+            "{:?} != {:?}",
+            one,
+            two,
+        );
+    });
+);
+
 pub mod node;
 pub mod parser;
+pub mod writer;
 
 pub use crate::node::Node;
 pub use crate::parser::Parser;
+pub use crate::writer::{Options, Writer};
 
 /// A document.
 pub type Document = node::element::SVG;
 
+/// Load a document.
+///
+/// See `Document::parse` for the nodes that are kept.
+pub fn load<T>(path: T) -> Result<Document>
+where
+    T: AsRef<Path>,
+{
+    let mut content = String::new();
+    let mut file = File::open(path)?;
+    file.read_to_string(&mut content)?;
+    Document::parse(&content).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
 /// Open a document.
 pub fn open<T>(path: T, content: &'_ mut String) -> Result<Parser<'_>>
 where
@@ -91,22 +121,48 @@ pub fn read(content: &'_ str) -> Result<Parser<'_>> {
 }
 
 /// Save a document.
+#[inline]
 pub fn save<T, U>(path: T, document: &U) -> Result<()>
 where
     T: AsRef<Path>,
     U: Node,
 {
-    let mut file = File::create(path)?;
-    file.write_all(&document.to_string().into_bytes())
+    save_with(path, document, &Options::default())
+}
+
+/// Save a document according to options.
+pub fn save_with<T, U>(path: T, document: &U, options: &Options) -> Result<()>
+where
+    T: AsRef<Path>,
+    U: Node,
+{
+    write_with(File::create(path)?, document, options)
 }
 
 /// Write a document.
-pub fn write<T, U>(mut target: T, document: &U) -> Result<()>
+#[inline]
+pub fn write<T, U>(target: T, document: &U) -> Result<()>
+where
+    T: Write,
+    U: Node,
+{
+    write_with(target, document, &Options::default())
+}
+
+/// Write a document according to options.
+///
+/// The document is written incrementally without being converted into a
+/// string first.
+pub fn write_with<T, U>(target: T, document: &U, options: &Options) -> Result<()>
 where
     T: Write,
     U: Node,
 {
-    target.write_all(&document.to_string().into_bytes())
+    let mut adapter = writer::Adapter::new(BufWriter::new(target));
+    let result = Writer::new(&mut adapter, options).node(document);
+    let mut target = adapter.into_inner()?;
+    result.map_err(|_| Error::other("failed to serialize a node"))?;
+    target.flush()
 }
 
 #[cfg(test)]
@@ -116,7 +172,23 @@ mod tests {
 
     use crate::parser::{Event, Parser};
 
-    const TEST_PATH: &'static str = "tests/fixtures/benton.svg";
+    const TEST_PATH: &str = "tests/fixtures/benton.svg";
+
+    #[test]
+    fn load() {
+        use crate::node::Node;
+
+        let document = crate::load(self::TEST_PATH).unwrap();
+        assert_eq!(document.get_attributes().unwrap()["id"], "Layer_1");
+        let children = document
+            .get_children()
+            .unwrap()
+            .iter()
+            .filter(|child| child.get_attributes().is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(children.len(), 4);
+        assert!(children.iter().all(|child| child.get_name() == "path"));
+    }
 
     #[test]
     fn open() {
@@ -133,6 +205,29 @@ mod tests {
         exercise(crate::read(&content).unwrap());
     }
 
+    #[test]
+    fn write() {
+        struct Full;
+
+        impl std::io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::WriteZero.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let document = crate::load(self::TEST_PATH).unwrap();
+        let mut content = Vec::new();
+        crate::write(&mut content, &document).unwrap();
+        assert_eq!(String::from_utf8(content).unwrap(), document.to_string());
+
+        let error = crate::write(Full, &document).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    }
+
     fn exercise<'l>(mut parser: Parser<'l>) {
         macro_rules! test(
             ($matcher:pat) => (match parser.next().unwrap() {
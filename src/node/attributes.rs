@@ -0,0 +1,299 @@
+use std::fmt;
+use std::ops::Index;
+use std::slice;
+use std::vec;
+
+use crate::node::Value;
+
+/// Attributes.
+///
+/// The attributes are kept in the order in which they were inserted, which is
+/// disregarded when comparing them.
+#[derive(Clone, Default)]
+pub struct Attributes {
+    pairs: Vec<(String, Value)>,
+}
+
+/// An iterator over attributes.
+pub struct Iter<'l>(slice::Iter<'l, (String, Value)>);
+
+/// An iterator over attributes as mutable.
+pub struct IterMut<'l>(slice::IterMut<'l, (String, Value)>);
+
+/// An iterator over owned attributes.
+pub struct IntoIter(vec::IntoIter<(String, Value)>);
+
+impl Attributes {
+    /// Create attributes.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Return the number of attributes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Check if there are no attributes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Check if an attribute is present.
+    #[inline]
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Return the value of an attribute.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.position(name).map(|index| &self.pairs[index].1)
+    }
+
+    /// Return the value of an attribute as mutable.
+    #[inline]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.position(name).map(|index| &mut self.pairs[index].1)
+    }
+
+    /// Insert an attribute.
+    ///
+    /// If the attribute is already present, its value is replaced in place,
+    /// and the old value is returned.
+    pub fn insert(&mut self, name: String, value: Value) -> Option<Value> {
+        match self.position(&name) {
+            Some(index) => Some(std::mem::replace(&mut self.pairs[index].1, value)),
+            _ => {
+                self.pairs.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Remove an attribute preserving the order of the others.
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        self.position(name).map(|index| self.pairs.remove(index).1)
+    }
+
+    /// Retain only the attributes satisfying a predicate.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&str, &mut Value) -> bool,
+    {
+        self.pairs
+            .retain_mut(|(name, value)| predicate(name, value));
+    }
+
+    /// Remove all attributes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+    }
+
+    /// Sort the attributes by name.
+    #[inline]
+    pub fn sort(&mut self) {
+        self.pairs.sort_by(|one, other| one.0.cmp(&other.0));
+    }
+
+    /// Iterate over the attributes.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.pairs.iter())
+    }
+
+    /// Iterate over the attributes with the values as mutable.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut(self.pairs.iter_mut())
+    }
+
+    /// Iterate over the names.
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.pairs.iter().map(|(name, _)| name)
+    }
+
+    /// Iterate over the values.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.pairs.iter().map(|(_, value)| value)
+    }
+
+    /// Iterate over the values as mutable.
+    #[inline]
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Value> {
+        self.pairs.iter_mut().map(|(_, value)| value)
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.pairs.iter().position(|(other, _)| other == name)
+    }
+}
+
+impl fmt::Debug for Attributes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for Attributes {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(name, value)| other.get(name) == Some(value))
+    }
+}
+
+impl Eq for Attributes {}
+
+impl<T, U> Extend<(T, U)> for Attributes
+where
+    T: Into<String>,
+    U: Into<Value>,
+{
+    fn extend<I>(&mut self, iterator: I)
+    where
+        I: IntoIterator<Item = (T, U)>,
+    {
+        for (name, value) in iterator {
+            self.insert(name.into(), value.into());
+        }
+    }
+}
+
+impl<T, U> FromIterator<(T, U)> for Attributes
+where
+    T: Into<String>,
+    U: Into<Value>,
+{
+    fn from_iter<I>(iterator: I) -> Self
+    where
+        I: IntoIterator<Item = (T, U)>,
+    {
+        let mut attributes = Attributes::new();
+        attributes.extend(iterator);
+        attributes
+    }
+}
+
+impl Index<&str> for Attributes {
+    type Output = Value;
+
+    #[inline]
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name).expect("found no such attribute")
+    }
+}
+
+impl<'l> IntoIterator for &'l Attributes {
+    type Item = (&'l String, &'l Value);
+    type IntoIter = Iter<'l>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'l> IntoIterator for &'l mut Attributes {
+    type Item = (&'l String, &'l mut Value);
+    type IntoIter = IterMut<'l>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl IntoIterator for Attributes {
+    type Item = (String, Value);
+    type IntoIter = IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.pairs.into_iter())
+    }
+}
+
+impl<'l> Iterator for Iter<'l> {
+    type Item = (&'l String, &'l Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(name, value)| (name, value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'l> Iterator for IterMut<'l> {
+    type Item = (&'l String, &'l mut Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(name, value)| (&*name, value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl Iterator for IntoIter {
+    type Item = (String, Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Attributes;
+
+    #[test]
+    fn attributes_order() {
+        let mut attributes = Attributes::new();
+        attributes.insert("xmlns".into(), "foo".into());
+        attributes.insert("width".into(), 10.into());
+        attributes.insert("height".into(), 20.into());
+        assert_eq!(attributes.insert("width".into(), 30.into()).unwrap(), "10");
+        assert_eq!(
+            attributes.keys().collect::<Vec<_>>(),
+            &["xmlns", "width", "height"],
+        );
+        assert_eq!(attributes["width"], "30");
+
+        assert_eq!(attributes.remove("xmlns").unwrap(), "foo");
+        assert_eq!(attributes.keys().collect::<Vec<_>>(), &["width", "height"]);
+
+        attributes.sort();
+        assert_eq!(attributes.keys().collect::<Vec<_>>(), &["height", "width"]);
+    }
+
+    #[test]
+    fn attributes_eq() {
+        let one = [("x", 1), ("y", 2)].into_iter().collect::<Attributes>();
+        let two = [("y", 2), ("x", 1)].into_iter().collect::<Attributes>();
+        let three = [("x", 1), ("y", 3)].into_iter().collect::<Attributes>();
+        assert_eq!(one, two);
+        assert_ne!(one, three);
+        assert_ne!(one, Attributes::new());
+    }
+}
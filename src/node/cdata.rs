@@ -0,0 +1,68 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::node::Node;
+
+/// A CDATA node.
+#[derive(Clone, Debug)]
+pub struct CData {
+    content: String,
+}
+
+impl CData {
+    /// Create a node.
+    #[inline]
+    pub fn new<T>(content: T) -> Self
+    where
+        T: Into<String>,
+    {
+        CData {
+            content: content.into(),
+        }
+    }
+}
+
+impl fmt::Display for CData {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "<![CDATA[{}]]>",
+            self.content.replace("]]>", "]]]]><![CDATA[>"),
+        )
+    }
+}
+
+impl Node for CData {
+    #[inline]
+    fn get_name(&self) -> &str {
+        "cdata"
+    }
+
+    #[inline]
+    fn is_bare(&self) -> bool {
+        true
+    }
+}
+
+impl super::NodeDefaultHash for CData {
+    #[inline]
+    fn default_hash(&self, state: &mut DefaultHasher) {
+        self.content.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CData;
+
+    #[test]
+    fn cdata_display() {
+        let cdata = CData::new("a < b && c");
+        assert_eq!(cdata.to_string(), "<![CDATA[a < b && c]]>");
+
+        let cdata = CData::new("a[b[c]]>d");
+        assert_eq!(cdata.to_string(), "<![CDATA[a[b[c]]]]><![CDATA[>d]]>");
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::node::{Node, Value};
+
+/// A declaration node.
+#[derive(Clone, Debug)]
+pub struct Declaration {
+    content: String,
+}
+
+impl Declaration {
+    /// Create a node.
+    #[inline]
+    pub fn new<T>(content: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            content: content.into(),
+        }
+    }
+}
+
+impl fmt::Display for Declaration {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "<!{}>", self.content)
+    }
+}
+
+impl Node for Declaration {
+    #[inline]
+    fn append<T>(&mut self, _: T)
+    where
+        T: Into<Box<dyn Node>>,
+    {
+    }
+
+    #[inline]
+    fn assign<T, U>(&mut self, _: T, _: U)
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+    }
+
+    #[inline]
+    fn get_name(&self) -> &str {
+        "declaration"
+    }
+}
+
+impl super::NodeDefaultHash for Declaration {
+    #[inline]
+    fn default_hash(&self, state: &mut DefaultHasher) {
+        self.content.hash(state);
+    }
+}
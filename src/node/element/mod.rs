@@ -7,7 +7,10 @@ use std::collections::hash_map::DefaultHasher;
 use std::fmt;
 use std::hash::Hash;
 
+use crate::node::namespace::{Name, Scope};
+use crate::node::value::{self, Color, Length, Paint, PreserveAspectRatio, Transform, ViewBox};
 use crate::node::{Attributes, Children, Node, Value};
+use crate::writer::{Options, Writer};
 
 pub mod path;
 pub mod tag;
@@ -33,6 +36,23 @@ impl Element {
         }
     }
 
+    /// Create an element in a namespace.
+    ///
+    /// The namespace is declared on the element itself and bound to the
+    /// prefix if given or made the default one otherwise.
+    pub fn with_namespace<T>(prefix: Option<&str>, namespace: T, local: &str) -> Self
+    where
+        T: Into<Value>,
+    {
+        let (name, declaration) = match prefix {
+            Some(prefix) => (format!("{}:{}", prefix, local), format!("xmlns:{}", prefix)),
+            _ => (local.to_string(), "xmlns".to_string()),
+        };
+        let mut element = Element::new(name);
+        element.attributes.insert(declaration, namespace.into());
+        element
+    }
+
     /// Return the name.
     #[inline]
     pub fn get_name(&self) -> &str {
@@ -62,32 +82,282 @@ impl Element {
     pub fn get_children_mut(&mut self) -> &mut Children {
         &mut self.children
     }
-}
 
-impl fmt::Display for Element {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        write!(formatter, "<{}", self.name)?;
-        let mut attributes = self.attributes.iter().collect::<Vec<_>>();
-        attributes.sort_by_key(|pair| pair.0.as_str());
-        for (name, value) in attributes {
-            write!(formatter, r#" {}="{}""#, name, escape(value))?;
-        }
-        if self.children.is_empty() {
-            return write!(formatter, "/>");
+    /// Return an attribute as a color.
+    ///
+    /// `None` is returned if the attribute is absent or is not a color.
+    pub fn get_color(&self, name: &str) -> Option<Color> {
+        self.attributes
+            .get(name)
+            .and_then(|value| Color::parse(value).ok())
+    }
+
+    /// Return an attribute as a length.
+    ///
+    /// `None` is returned if the attribute is absent or is not a length.
+    pub fn get_length(&self, name: &str) -> Option<Length> {
+        self.attributes
+            .get(name)
+            .and_then(|value| Length::parse(value).ok())
+    }
+
+    /// Return an attribute as a paint.
+    ///
+    /// `None` is returned if the attribute is absent or is not a paint.
+    pub fn get_paint(&self, name: &str) -> Option<Paint> {
+        self.attributes
+            .get(name)
+            .and_then(|value| Paint::parse(value).ok())
+    }
+
+    /// Return an attribute as a transform list.
+    ///
+    /// `None` is returned if the attribute is absent or is not a transform list.
+    pub fn get_transform(&self, name: &str) -> Option<Transform> {
+        self.attributes
+            .get(name)
+            .and_then(|value| Transform::parse(value).ok())
+    }
+
+    /// Return the `viewBox` attribute.
+    ///
+    /// `None` is returned if the attribute is absent or is not a view box.
+    pub fn get_view_box(&self) -> Option<ViewBox> {
+        self.attributes
+            .get("viewBox")
+            .and_then(|value| ViewBox::parse(value).ok())
+    }
+
+    /// Return the `preserveAspectRatio` attribute.
+    ///
+    /// The default is returned if the attribute is absent or is invalid.
+    pub fn get_preserve_aspect_ratio(&self) -> PreserveAspectRatio {
+        self.attributes
+            .get("preserveAspectRatio")
+            .and_then(|value| PreserveAspectRatio::parse(value).ok())
+            .unwrap_or_default()
+    }
+
+    /// Return the `style` attribute.
+    ///
+    /// `None` is returned if the attribute is absent or is invalid.
+    pub fn get_style(&self) -> Option<value::Style> {
+        self.attributes
+            .get("style")
+            .and_then(|value| value::Style::parse(value).ok())
+    }
+
+    /// Return a property of the `style` attribute.
+    ///
+    /// Property names are matched case-insensitively, and the `!important`
+    /// flag is removed from the value.
+    pub fn get_property(&self, name: &str) -> Option<String> {
+        self.get_style().and_then(|style| {
+            style
+                .get(name)
+                .map(|value| value::split_important(value).0.into())
+        })
+    }
+
+    /// Set a property of the `style` attribute.
+    ///
+    /// An existing property is matched case-insensitively. An invalid `style`
+    /// attribute is replaced.
+    pub fn set_property<T, U>(&mut self, name: T, value: U)
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        let mut style = self.get_style().unwrap_or_default();
+        style.insert(name, value);
+        self.set_style(style);
+    }
+
+    /// Remove a property of the `style` attribute returning its value.
+    ///
+    /// The attribute is removed once it is empty.
+    pub fn remove_property(&mut self, name: &str) -> Option<String> {
+        let mut style = self.get_style()?;
+        let value = style.remove(name)?;
+        self.set_style(style);
+        Some(value)
+    }
+
+    /// Move the presentation properties of the `style` attribute into
+    /// attributes, overwriting existing ones.
+    ///
+    /// Property names are matched case-insensitively, and `!important`
+    /// declarations stay in `style`. An invalid `style` attribute is left
+    /// intact.
+    pub fn move_style_to_attributes(&mut self) {
+        let style = match self.get_style() {
+            Some(style) => style,
+            _ => return,
+        };
+        let mut rest = value::Style::new();
+        for (name, value) in style.iter() {
+            let attribute = name.to_ascii_lowercase();
+            if value::is_presentation_attribute(&attribute) && !value::split_important(value).1 {
+                self.attributes.insert(attribute, value.into());
+            } else {
+                rest.insert(name, value);
+            }
         }
-        write!(formatter, ">")?;
-        let mut bare = false;
-        for child in self.children.iter() {
-            bare = child.is_bare() && !formatter.alternate();
-            if !bare {
-                writeln!(formatter)?;
+        self.set_style(rest);
+    }
+
+    /// Move the presentation attributes into the `style` attribute, keeping
+    /// existing properties, which are matched case-insensitively.
+    ///
+    /// An invalid `style` attribute is left intact together with the
+    /// attributes.
+    pub fn move_attributes_to_style(&mut self) {
+        let mut style = match self.attributes.get("style") {
+            Some(value) => match value::Style::parse(value) {
+                Ok(style) => style,
+                _ => return,
+            },
+            _ => value::Style::new(),
+        };
+        self.attributes.retain(|name, value| {
+            if !value::is_presentation_attribute(name) {
+                return true;
             }
-            write!(formatter, "{}", child)?;
+            if style.get(name).is_none() {
+                style.insert(name, value.to_string());
+            }
+            false
+        });
+        self.set_style(style);
+    }
+
+    /// Return an iterator over the distinct classes.
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        let content = self.attributes.get("class").map_or("", |value| value);
+        content
+            .split_whitespace()
+            .enumerate()
+            .filter(move |&(i, class)| {
+                !content
+                    .split_whitespace()
+                    .take(i)
+                    .any(|other| other == class)
+            })
+            .map(|(_, class)| class)
+    }
+
+    /// Check if a class is present.
+    pub fn has_class(&self, name: &str) -> bool {
+        self.classes().any(|class| class == name)
+    }
+
+    /// Add a class returning `true` if it was absent.
+    ///
+    /// Names that are empty or contain whitespace are ignored.
+    pub fn add_class(&mut self, name: &str) -> bool {
+        if !check_class(name) || self.has_class(name) {
+            return false;
+        }
+        let mut classes = self.classes().collect::<Vec<_>>();
+        classes.push(name);
+        let classes = classes.join(" ");
+        self.attributes.insert("class".into(), classes.into());
+        true
+    }
+
+    /// Remove a class returning `true` if it was present.
+    ///
+    /// The attribute is removed once it is empty.
+    pub fn remove_class(&mut self, name: &str) -> bool {
+        if !self.has_class(name) {
+            return false;
+        }
+        let classes = self
+            .classes()
+            .filter(|class| *class != name)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if classes.is_empty() {
+            self.attributes.remove("class");
+        } else {
+            self.attributes.insert("class".into(), classes.into());
         }
-        if !bare {
-            writeln!(formatter)?;
+        true
+    }
+
+    /// Add a class if it is absent and remove it otherwise returning `true`
+    /// if it is present afterwards.
+    ///
+    /// Names that are empty or contain whitespace are ignored.
+    pub fn toggle_class(&mut self, name: &str) -> bool {
+        if !check_class(name) {
+            return false;
         }
-        write!(formatter, "</{}>", self.name)
+        !self.remove_class(name) && self.add_class(name)
+    }
+
+    fn set_style(&mut self, style: value::Style) {
+        if style.is_empty() {
+            self.attributes.remove("style");
+        } else {
+            self.attributes.insert("style".into(), style.into());
+        }
+    }
+
+    /// Resolve the qualified name in a scope of namespace declarations.
+    ///
+    /// The scope is expected to include the declarations of the element
+    /// itself; see `Scope::push`. `None` is returned if the prefix is not
+    /// bound.
+    #[inline]
+    pub fn get_qualified_name<'l>(&'l self, scope: &'l Scope) -> Option<Name<'l>> {
+        scope.resolve_element(&self.name)
+    }
+
+    /// Iterate over the attributes with the qualified names resolved in a
+    /// scope of namespace declarations.
+    ///
+    /// The scope is expected to include the declarations of the element
+    /// itself; see `Scope::push`. Attributes with unbound prefixes are
+    /// skipped.
+    pub fn get_qualified_attributes<'l>(
+        &'l self,
+        scope: &'l Scope,
+    ) -> impl Iterator<Item = (Name<'l>, &'l Value)> {
+        self.attributes
+            .iter()
+            .filter_map(|(name, value)| Some((scope.resolve_attribute(name)?, value)))
+    }
+
+    /// Return an attribute by its qualified name resolved in a scope of
+    /// namespace declarations.
+    ///
+    /// The scope is expected to include the declarations of the element
+    /// itself; see `Scope::push`.
+    #[inline]
+    pub fn get_qualified_attribute(
+        &self,
+        scope: &Scope,
+        namespace: Option<&str>,
+        local: &str,
+    ) -> Option<&Value> {
+        scope.find(&self.attributes, namespace, local)
+    }
+
+    /// Remove the descendants and attributes in a namespace together with
+    /// the declarations binding it.
+    pub fn remove_namespace(&mut self, namespace: &str) {
+        let mut scope = Scope::new();
+        scope.push(&self.attributes);
+        crate::node::namespace::remove(self, &mut scope, namespace);
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let options = Options::default();
+        let bareable = formatter.alternate();
+        Writer::new(formatter, &options).element(self, bareable)
     }
 }
 
@@ -133,6 +403,11 @@ impl Node for Element {
     fn get_children_mut(&mut self) -> Option<&mut Children> {
         Self::get_children_mut(self).into()
     }
+
+    #[inline]
+    fn serialize(&self, writer: &mut Writer) -> fmt::Result {
+        writer.element(self, false)
+    }
 }
 
 macro_rules! implement_nested(
@@ -140,6 +415,13 @@ macro_rules! implement_nested(
         implement_nested!($struct_name::$field_name []);
     );
     ($struct_name:ident::$field_name:ident [$($indicator_name:ident),*]) => (
+        implement_nested!($struct_name::$field_name [$($indicator_name),*] []);
+    );
+    (
+        $struct_name:ident::$field_name:ident
+        [$($indicator_name:ident),*]
+        [$($prolog:ident, $epilog:ident)?]
+    ) => (
         impl $struct_name {
             /// Append a node.
             pub fn add<T>(mut self, node: T) -> Self
@@ -205,6 +487,21 @@ macro_rules! implement_nested(
                 self.$field_name.get_children_mut().into()
             }
 
+            fn serialize(&self, writer: &mut Writer) -> std::fmt::Result {
+                $(
+                    for node in self.$prolog.iter() {
+                        node.serialize(writer)?;
+                    }
+                )?
+                writer.element(&self.$field_name, self.is_bareable())?;
+                $(
+                    for node in self.$epilog.iter() {
+                        node.serialize(writer)?;
+                    }
+                )?
+                Ok(())
+            }
+
             $(
                 #[inline]
                 fn $indicator_name(&self) -> bool {
@@ -232,11 +529,8 @@ macro_rules! implement_nested(
         impl std::fmt::Display for $struct_name {
             #[inline]
             fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                if self.is_bareable() {
-                    write!(formatter, "{:#}", self.$field_name)
-                } else {
-                    self.$field_name.fmt(formatter)
-                }
+                let options = Options::default();
+                Writer::new(formatter, &options).node(self)
             }
         }
 
@@ -504,12 +798,76 @@ macro_rules! implement {
     )*);
 }
 
-implement! {
-    #[doc = "An [`svg`](https://www.w3.org/TR/SVG/struct.html#SVGElement) element."]
-    struct SVG [is_bareable] [] [inner] {
+/// An [`svg`](https://www.w3.org/TR/SVG/struct.html#SVGElement) element.
+///
+/// The nodes preceding and following the element in a document are kept in its
+/// prolog and epilog, respectively, and written around it.
+#[derive(Clone, Debug)]
+pub struct SVG {
+    inner: Element,
+    prolog: Children,
+    epilog: Children,
+}
+
+impl SVG {
+    /// Create a node.
+    #[inline]
+    pub fn new() -> Self {
+        let mut inner = Element::new(tag::SVG);
         inner.assign("xmlns", "http://www.w3.org/2000/svg");
+        SVG {
+            inner,
+            prolog: vec![],
+            epilog: vec![],
+        }
+    }
+
+    /// Return the nodes preceding the element.
+    #[inline]
+    pub fn get_prolog(&self) -> &Children {
+        &self.prolog
+    }
+
+    /// Return the nodes preceding the element as mutable.
+    #[inline]
+    pub fn get_prolog_mut(&mut self) -> &mut Children {
+        &mut self.prolog
+    }
+
+    /// Return the nodes following the element.
+    #[inline]
+    pub fn get_epilog(&self) -> &Children {
+        &self.epilog
+    }
+
+    /// Return the nodes following the element as mutable.
+    #[inline]
+    pub fn get_epilog_mut(&mut self) -> &mut Children {
+        &mut self.epilog
+    }
+}
+
+impl Default for SVG {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl super::NodeDefaultHash for SVG {
+    fn default_hash(&self, state: &mut DefaultHasher) {
+        self.prolog
+            .iter()
+            .for_each(|child| child.default_hash(state));
+        self.inner.default_hash(state);
+        self.epilog
+            .iter()
+            .for_each(|child| child.default_hash(state));
     }
+}
+
+implement_nested! { SVG::inner [is_bareable] [prolog, epilog] }
 
+implement! {
     #[doc = "A [`script`](https://www.w3.org/TR/SVG/script.html#ScriptElement) element."]
     struct Script [is_bareable] [T: Into<String>] [inner, content: T] {
         inner.append(crate::node::Text::new(content));
@@ -541,15 +899,101 @@ implement! {
     }
 }
 
-fn escape(value: &str) -> String {
-    crate::node::text::escape(value)
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
+impl SVG {
+    /// Parse a document.
+    ///
+    /// The nodes outside of the root element, which are the XML declaration,
+    /// the document type declaration, and comments and instructions, are kept
+    /// in the prolog and epilog and hence written back. Entities declared in
+    /// the document type declaration are expanded. Whitespace in text is kept
+    /// as is within elements where `xml:space` is set to `preserve`.
+    pub fn parse(content: &str) -> crate::parser::Result<Self> {
+        use crate::node::{CData, Comment, Declaration, Instruction, Text};
+        use crate::parser::{Error, Event, Parser, Whitespace};
+
+        macro_rules! raise(
+            ($parser:expr, $($argument:tt)*) => (
+                return Err(Error::new($parser.position(), format!($($argument)*)))
+            );
+        );
+
+        let mut parser = Parser::new(content)
+            .well_formed(true)
+            .whitespace(Whitespace::XmlSpace);
+        let mut stack: Vec<Element> = vec![];
+        let mut root = None;
+        let (mut prolog, mut epilog) = (vec![], vec![]);
+        while let Some(event) = parser.next() {
+            let element = match event {
+                Event::Error(error) => return Err(error),
+                Event::Tag(name, tag::Type::Start, attributes) => {
+                    let mut element = Element::new(name);
+                    element.attributes = attributes;
+                    stack.push(element);
+                    continue;
+                }
+                Event::Tag(name, tag::Type::End, _) => stack.pop().ok_or_else(|| {
+                    Error::new(
+                        parser.position(),
+                        format!("found an end tag '{}' without a start tag", name),
+                    )
+                })?,
+                Event::Tag(name, tag::Type::Empty, attributes) => {
+                    let mut element = Element::new(name);
+                    element.attributes = attributes;
+                    element
+                }
+                event => {
+                    let node: Box<dyn Node> = match event {
+                        Event::Text(content) => Box::new(Text::new(content)),
+                        Event::CData(content) => Box::new(CData::new(content)),
+                        Event::Comment(content) => {
+                            let content = &content[4..(content.len() - 3)];
+                            Box::new(Comment::new(content.trim()))
+                        }
+                        Event::Declaration(content) => {
+                            let content = &content[2..(content.len() - 1)];
+                            Box::new(Declaration::new(content))
+                        }
+                        Event::Instruction(content) => {
+                            let content = &content[2..(content.len() - 2)];
+                            Box::new(Instruction::new(content))
+                        }
+                        _ => unreachable!(),
+                    };
+                    match stack.last_mut() {
+                        Some(parent) => parent.append(node),
+                        _ if root.is_none() => prolog.push(node),
+                        _ => epilog.push(node),
+                    }
+                    continue;
+                }
+            };
+            match stack.last_mut() {
+                Some(parent) => parent.append(element),
+                _ if root.is_none() => root = Some(element),
+                _ => raise!(parser, "found more than one root element"),
+            }
+        }
+        match root {
+            Some(inner) if inner.name == tag::SVG => Ok(SVG {
+                inner,
+                prolog,
+                epilog,
+            }),
+            Some(inner) => raise!(parser, "expected an svg element, found '{}'", inner.name),
+            _ => raise!(parser, "found no root element"),
+        }
+    }
+}
+
+fn check_class(name: &str) -> bool {
+    !name.is_empty() && !name.contains(char::is_whitespace)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Element, Rectangle, Style, Title};
+    use super::{Element, Rectangle, Style, Title, SVG};
     use crate::node::element;
 
     #[test]
@@ -572,6 +1016,187 @@ mod tests {
         );
     }
 
+    #[test]
+    fn element_get_paint() {
+        use crate::node::value::{Color, Paint};
+
+        let element = Rectangle::new()
+            .set("fill", "#FF780088")
+            .set(
+                "stroke",
+                Paint::Url("#foo".into(), Some(Box::new(Paint::None))),
+            )
+            .set("stop-color", Color::rgb(0, 0, 128));
+        assert_eq!(
+            element.get_color("fill"),
+            Some(Color::rgba(255, 120, 0, 136))
+        );
+        assert_eq!(
+            element.get_paint("stroke"),
+            Some(Paint::Url("#foo".into(), Some(Box::new(Paint::None)))),
+        );
+        assert_eq!(element.get_attributes()["stop-color"], "navy");
+        assert_eq!(element.get_color("stroke"), None);
+    }
+
+    #[test]
+    fn element_get_length() {
+        use crate::node::value::{Length, Unit};
+
+        let element = Rectangle::new().set("width", "10mm").set("x", "foo");
+        assert_eq!(
+            element.get_length("width"),
+            Some(Length::new(10.0, Unit::Millimeter)),
+        );
+        assert_eq!(element.get_length("x"), None);
+        assert_eq!(element.get_length("y"), None);
+
+        let element = element.set("height", Length::new(50.0, Unit::Percent));
+        assert_eq!(element.get_attributes()["height"], "50%");
+    }
+
+    #[test]
+    fn element_get_view_box() {
+        use crate::node::value::{Align, MeetOrSlice, ViewBox};
+
+        let element = SVG::new()
+            .set("viewBox", (0, 0, 70, 70))
+            .set("preserveAspectRatio", "xMinYMax slice");
+        assert_eq!(
+            element.get_view_box(),
+            Some(ViewBox::new(0.0, 0.0, 70.0, 70.0)),
+        );
+        let aspect = element.get_preserve_aspect_ratio();
+        assert_eq!(aspect.align, Align::XMinYMax);
+        assert_eq!(aspect.meet_or_slice, MeetOrSlice::Slice);
+
+        let element = element
+            .set("viewBox", "0 0 -70 70")
+            .set("preserveAspectRatio", "foo");
+        assert_eq!(element.get_view_box(), None);
+        assert_eq!(element.get_preserve_aspect_ratio(), Default::default());
+    }
+
+    #[test]
+    fn element_classes() {
+        let mut element = Rectangle::new().set("class", " foo\tbar  foo baz ");
+        assert_eq!(
+            element.classes().collect::<Vec<_>>(),
+            &["foo", "bar", "baz"]
+        );
+        assert!(element.has_class("bar"));
+        assert!(!element.has_class("ba"));
+
+        assert!(!element.add_class("foo"));
+        assert!(element.add_class("qux"));
+        assert!(!element.add_class("a b"));
+        assert!(!element.add_class(""));
+        assert_eq!(element.get_attributes()["class"], "foo bar baz qux");
+
+        assert!(element.remove_class("bar"));
+        assert!(!element.remove_class("bar"));
+        assert!(!element.toggle_class("foo"));
+        assert!(element.toggle_class("bar"));
+        assert_eq!(element.get_attributes()["class"], "baz qux bar");
+
+        for name in ["baz", "qux", "bar"] {
+            element.remove_class(name);
+        }
+        assert!(!element.get_attributes().contains_key("class"));
+        assert_eq!(element.classes().count(), 0);
+    }
+
+    #[test]
+    fn element_style() {
+        let mut element = Rectangle::new()
+            .set("fill", "blue")
+            .set("style", "fill: red; cursor: pointer; foo: bar")
+            .set("width", 10)
+            .set("stroke-width", 2);
+        assert_eq!(element.get_property("fill"), Some("red".into()));
+        assert_eq!(element.get_property("stroke-width"), None);
+
+        element.set_property("opacity", "0.5");
+        assert_eq!(
+            element.get_attributes()["style"],
+            "fill:red;cursor:pointer;foo:bar;opacity:0.5",
+        );
+        assert_eq!(element.remove_property("cursor"), Some("pointer".into()));
+        assert_eq!(element.remove_property("cursor"), None);
+
+        element.move_style_to_attributes();
+        assert_eq!(element.get_attributes()["fill"], "red");
+        assert_eq!(element.get_attributes()["opacity"], "0.5");
+        assert_eq!(element.get_attributes()["style"], "foo:bar");
+
+        element.move_attributes_to_style();
+        assert_eq!(
+            element.get_attributes().keys().collect::<Vec<_>>(),
+            &["style", "width"],
+        );
+        assert_eq!(
+            element.get_attributes()["style"],
+            "foo:bar;fill:red;stroke-width:2;opacity:0.5",
+        );
+
+        assert_eq!(element.remove_property("foo"), Some("bar".into()));
+        for name in ["fill", "stroke-width", "opacity"] {
+            element.remove_property(name);
+        }
+        assert!(!element.get_attributes().contains_key("style"));
+
+        let mut element = Rectangle::new().set(
+            "style",
+            "fill: red !important; Stroke: blue; STROKE-WIDTH: 2",
+        );
+        element.move_style_to_attributes();
+        assert_eq!(element.get_attributes()["style"], "fill:red !important");
+        assert!(!element.get_attributes().contains_key("fill"));
+        assert_eq!(element.get_attributes()["stroke"], "blue");
+        assert_eq!(element.get_attributes()["stroke-width"], "2");
+
+        let mut element = Rectangle::new()
+            .set("fill", "blue")
+            .set("stroke", "green")
+            .set("style", "Fill: red; STROKE: black !important");
+        assert_eq!(element.get_property("FILL"), Some("red".into()));
+        assert_eq!(element.get_property("stroke"), Some("black".into()));
+        element.set_property("FILL", "yellow");
+        assert_eq!(
+            element.get_attributes()["style"],
+            "Fill:yellow;STROKE:black !important",
+        );
+        element.move_attributes_to_style();
+        assert_eq!(
+            element.get_attributes()["style"],
+            "Fill:yellow;STROKE:black !important",
+        );
+        assert!(!element.get_attributes().contains_key("fill"));
+        assert!(!element.get_attributes().contains_key("stroke"));
+    }
+
+    #[test]
+    fn element_get_transform() {
+        use crate::node::value::{Transform, TransformFunction};
+
+        let element = element::Group::new()
+            .set("transform", "translate(10, 20) scale(2)")
+            .set("gradientTransform", "skew(1)");
+        assert_eq!(
+            element
+                .get_transform("transform")
+                .unwrap()
+                .to_matrix()
+                .apply((1.0, 1.0)),
+            (12.0, 22.0),
+        );
+        assert_eq!(element.get_transform("gradientTransform"), None);
+
+        let transform = Transform::from(vec![TransformFunction::Rotate(45.0, 5.0, 5.0)]);
+        let element = element.set("transform", transform);
+        assert_eq!(element.get_attributes()["transform"], "rotate(45 5 5)");
+    }
+
     #[test]
     fn element_display() {
         use crate::node::Node;
@@ -586,7 +1211,7 @@ mod tests {
         assert_eq!(
             element.to_string().lines().collect::<Vec<_>>(),
             &[
-                r#"<foo c="green" s="12.5 13" x="-10" y="10px">"#,
+                r#"<foo x="-10" y="10px" s="12.5 13" c="green">"#,
                 "<bar/>",
                 "</foo>",
             ],
@@ -622,12 +1247,206 @@ mod tests {
         element.assign("d", r#""double""#);
         element.assign("m", r#""mixed'"#);
 
+        assert_eq!(
+            element.to_string(),
+            r#"<foo s="&apos;single&apos;" d="&quot;double&quot;" m="&quot;mixed&apos;"/>"#,
+        );
+
+        element.get_attributes_mut().sort();
+
         assert_eq!(
             element.to_string(),
             r#"<foo d="&quot;double&quot;" m="&quot;mixed&apos;" s="&apos;single&apos;"/>"#,
         );
     }
 
+    #[test]
+    fn svg_parse() {
+        let content = r#"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg">
+                <!-- foo -->
+                <g id="bar"><text>&lt;baz&gt;</text><path d="M1,2"/></g>
+            </svg>"#;
+        let document = SVG::parse(content).unwrap();
+
+        assert_eq!(
+            document.to_string().lines().collect::<Vec<_>>(),
+            &[
+                r#"<?xml version="1.0"?>"#,
+                r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+                "<!-- foo -->",
+                r#"<g id="bar">"#,
+                "<text>&lt;baz&gt;</text>",
+                r#"<path d="M1,2"/>"#,
+                "</g>",
+                "</svg>",
+            ],
+        );
+    }
+
+    #[test]
+    fn svg_parse_prolog() {
+        let content = [
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "svg11.dtd" ["#,
+            r#"  <!ENTITY foo "bar">"#,
+            "]>",
+            "<!-- baz -->",
+            "<?qux?>",
+            r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+            "<text>&foo;</text>",
+            "</svg>",
+            "<!-- quux -->",
+        ];
+        let document = SVG::parse(&content.join("\n")).unwrap();
+        assert_eq!(document.get_prolog().len(), 4);
+        assert_eq!(document.get_epilog().len(), 1);
+
+        let mut expected = content.to_vec();
+        expected[7] = "<text>bar</text>";
+        assert_eq!(document.to_string().lines().collect::<Vec<_>>(), expected);
+        let other = SVG::parse(&document.to_string()).unwrap();
+        assert_eq!(other.to_string(), document.to_string());
+    }
+
+    #[test]
+    fn svg_parse_malformed() {
+        macro_rules! test(
+            ($content:expr) => (assert!(SVG::parse($content).is_err()));
+        );
+
+        test!("");
+        test!("<!-- foo -->");
+        test!("<g/>");
+        test!("<svg><g></svg>");
+        test!("<svg></g></svg>");
+        test!("<svg>");
+        test!("<svg/><svg/>");
+        test!("</svg>");
+        test!("<svg/></svg>");
+    }
+
+    #[test]
+    fn svg_parse_whitespace() {
+        let content = concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+            "\n<text xml:space=\"preserve\"> <tspan>foo </tspan> <tspan> bar</tspan> </text>\n",
+            "<text> <tspan>baz </tspan> </text>\n",
+            "</svg>",
+        );
+        let document = SVG::parse(content).unwrap();
+
+        assert_eq!(
+            document.to_string().lines().collect::<Vec<_>>(),
+            &[
+                r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+                r#"<text xml:space="preserve"> <tspan>foo </tspan> <tspan> bar</tspan> </text>"#,
+                "<text>",
+                "<tspan>baz</tspan>",
+                "</text>",
+                "</svg>",
+            ],
+        );
+
+        let document = SVG::parse(&content.replace("<svg ", "<svg xml:space='preserve' ")).unwrap();
+
+        assert_eq!(
+            document.to_string(),
+            content.replace("<svg ", r#"<svg xml:space="preserve" "#),
+        );
+    }
+
+    #[test]
+    fn element_qualified_names() {
+        use crate::node::namespace::{self, Name, Scope};
+        use crate::node::Node;
+
+        let mut div = Element::with_namespace(Some("h"), namespace::XHTML, "div");
+        div.assign("class", "foo");
+        div.assign("xlink:href", "#bar");
+        div.assign("h:title", "baz");
+        let document = SVG::new()
+            .set("xmlns:xlink", namespace::XLINK)
+            .add(element::ForeignObject::new().add(div.clone()));
+        assert!(document
+            .to_string()
+            .contains(r##"<h:div xmlns:h="http://www.w3.org/1999/xhtml" class="foo" xlink:href="#bar" h:title="baz"/>"##));
+
+        let mut scope = Scope::new();
+        scope.push(document.get_attributes().unwrap());
+        assert_eq!(
+            document.get_qualified_name(&scope),
+            Some(Name {
+                namespace: Some(namespace::SVG),
+                local: "svg",
+            }),
+        );
+        assert_eq!(div.get_qualified_name(&scope), None);
+
+        scope.push(div.get_attributes());
+        assert_eq!(
+            div.get_qualified_name(&scope),
+            Some(Name {
+                namespace: Some(namespace::XHTML),
+                local: "div",
+            }),
+        );
+        assert_eq!(
+            div.get_qualified_attributes(&scope)
+                .map(|(name, _)| (name.namespace, name.local))
+                .collect::<Vec<_>>(),
+            &[
+                (Some(namespace::XMLNS), "h"),
+                (None, "class"),
+                (Some(namespace::XLINK), "href"),
+                (Some(namespace::XHTML), "title"),
+            ],
+        );
+        assert_eq!(
+            &**div
+                .get_qualified_attribute(&scope, Some(namespace::XLINK), "href")
+                .unwrap(),
+            "#bar",
+        );
+        assert!(div.get_qualified_attribute(&scope, None, "href").is_none());
+
+        let div = Element::with_namespace(None, namespace::XHTML, "div");
+        assert_eq!(
+            div.to_string(),
+            r#"<div xmlns="http://www.w3.org/1999/xhtml"/>"#
+        );
+    }
+
+    #[test]
+    fn svg_remove_namespace() {
+        let mut document = SVG::parse(
+            r#"<svg xmlns="http://www.w3.org/2000/svg"
+                    xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
+                    xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd"
+                    inkscape:version="1.0">
+                <sodipodi:namedview/>
+                <g inkscape:label="foo" xmlns:ink="http://www.inkscape.org/namespaces/inkscape">
+                    <ink:bar/>
+                    <path d="M1,2" ink:baz="qux"/>
+                </g>
+            </svg>"#,
+        )
+        .unwrap();
+        document.remove_namespace("http://www.inkscape.org/namespaces/inkscape");
+
+        assert_eq!(
+            document.to_string().lines().collect::<Vec<_>>(),
+            &[
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd">"#,
+                "<sodipodi:namedview/>",
+                "<g>",
+                r#"<path d="M1,2"/>"#,
+                "</g>",
+                "</svg>",
+            ],
+        );
+    }
+
     #[test]
     fn style_display() {
         let element = Style::new("* { font-family: foo; }");
@@ -636,5 +1455,36 @@ mod tests {
             element.to_string().lines().collect::<Vec<_>>(),
             &["<style>", "* { font-family: foo; }", "</style>"],
         );
+
+        let element = Style::new("a > b { content: '&'; }");
+
+        assert_eq!(
+            element.to_string().lines().collect::<Vec<_>>(),
+            &["<style>", "a &gt; b { content: '&amp;'; }", "</style>"],
+        );
+
+        let mut element = Style::new("");
+        element.get_children_mut().clear();
+        crate::node::Node::append(
+            &mut element,
+            crate::node::CData::new("a > b { content: ']]>'; }"),
+        );
+
+        assert_eq!(
+            element.to_string().lines().collect::<Vec<_>>(),
+            &[
+                "<style>",
+                "<![CDATA[a > b { content: ']]]]><![CDATA[>'; }]]>",
+                "</style>",
+            ],
+        );
+        let content = element.to_string();
+        let content = crate::parser::Parser::new(&content)
+            .filter_map(|event| match event {
+                crate::parser::Event::CData(content) => Some(content),
+                _ => None,
+            })
+            .collect::<String>();
+        assert_eq!(content, "a > b { content: ']]>'; }");
     }
 }
@@ -0,0 +1,317 @@
+use std::f32::consts::PI;
+
+use super::{Command, Data, Number, Point, Position, Segment};
+use crate::node::value::Matrix;
+
+impl Command {
+    /// Convert an elliptical arc starting at a given point into absolute cubic
+    /// Bézier curves deviating from it by at most a given tolerance.
+    ///
+    /// Arcs degenerating into straight lines are converted into absolute
+    /// `Line` commands. `None` is returned if the command is not an
+    /// elliptical arc.
+    pub fn arc_to_cubic_curves(&self, from: Point, tolerance: Number) -> Option<Vec<Command>> {
+        let (position, parameters) = match self {
+            Command::EllipticalArc(position, parameters) => (*position, parameters),
+            _ => return None,
+        };
+        let mut commands = Vec::new();
+        let mut current = from;
+        for chunk in parameters.chunks_exact(7) {
+            let to = match position {
+                Position::Absolute => (chunk[5], chunk[6]),
+                Position::Relative => (current.0 + chunk[5], current.1 + chunk[6]),
+            };
+            let arc = Arc::new(
+                current,
+                (chunk[0], chunk[1]),
+                chunk[2],
+                chunk[3] != 0.0,
+                chunk[4] != 0.0,
+                to,
+            );
+            convert(&mut commands, current, arc, to, tolerance);
+            current = to;
+        }
+        Some(commands)
+    }
+}
+
+impl Data {
+    /// Convert the elliptical arcs into absolute cubic Bézier curves deviating
+    /// from them by at most a given tolerance.
+    ///
+    /// The result is normalized; see `Data::normalize`.
+    pub fn arcs_to_cubic_curves(&self, tolerance: Number) -> Data {
+        let data = self.normalize();
+        let mut commands = Vec::with_capacity(data.len());
+        let mut segments = data.segments();
+        for command in data.iter() {
+            match command {
+                Command::Move(..) => commands.push(command.clone()),
+                _ => match segments.next() {
+                    Some(Segment::Arc {
+                        from,
+                        radii,
+                        rotation,
+                        large_arc,
+                        sweep,
+                        to,
+                    }) => {
+                        let arc = Arc::new(from, radii, rotation, large_arc, sweep, to);
+                        convert(&mut commands, from, arc, to, tolerance);
+                    }
+                    _ => commands.push(command.clone()),
+                },
+            }
+        }
+        Data::from(commands)
+    }
+}
+
+/// An elliptical arc in the center parameterization.
+///
+/// A point at an angle `θ` is `center + u cos(θ) + v sin(θ)`.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Arc {
+    pub center: Point,
+    pub u: Point,
+    pub v: Point,
+    pub start: Number,
+    pub sweep: Number,
+}
+
+impl Arc {
+    /// Convert from the endpoint parameterization.
+    ///
+    /// `None` is returned if the arc is to be treated as a straight line,
+    /// which is the case for coincident endpoints and zero radii.
+    pub fn new(
+        from: Point,
+        (rx, ry): (Number, Number),
+        rotation: Number,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    ) -> Option<Self> {
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if from == to || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+        let (x, y) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+        let lambda = (x * x) / (rx * rx) + (y * y) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let numerator = rx * rx * ry * ry - rx * rx * y * y - ry * ry * x * x;
+        let denominator = rx * rx * y * y + ry * ry * x * x;
+        let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+        let (cx, cy) = (coefficient * rx * y / ry, -coefficient * ry * x / rx);
+        let center = (
+            cos * cx - sin * cy + (from.0 + to.0) / 2.0,
+            sin * cx + cos * cy + (from.1 + to.1) / 2.0,
+        );
+
+        let start = angle((1.0, 0.0), ((x - cx) / rx, (y - cy) / ry));
+        let mut delta = angle(
+            ((x - cx) / rx, (y - cy) / ry),
+            ((-x - cx) / rx, (-y - cy) / ry),
+        );
+        if !sweep && delta > 0.0 {
+            delta -= 2.0 * PI;
+        } else if sweep && delta < 0.0 {
+            delta += 2.0 * PI;
+        }
+
+        Some(Arc {
+            center,
+            u: (rx * cos, rx * sin),
+            v: (-ry * sin, ry * cos),
+            start,
+            sweep: delta,
+        })
+    }
+
+    /// Return the point at an angle.
+    pub fn point(&self, angle: Number) -> Point {
+        let (sin, cos) = angle.sin_cos();
+        (
+            self.center.0 + self.u.0 * cos + self.v.0 * sin,
+            self.center.1 + self.u.1 * cos + self.v.1 * sin,
+        )
+    }
+
+    /// Check if an angle lies within the arc.
+    pub fn contains(&self, angle: Number) -> bool {
+        let offset = if self.sweep >= 0.0 {
+            angle - self.start
+        } else {
+            self.start - angle
+        };
+        offset.rem_euclid(2.0 * PI) <= self.sweep.abs()
+    }
+
+    /// Approximate by cubic Bézier curves given as pairs of control points
+    /// followed by end points.
+    pub fn cubic_curves(&self, tolerance: Number) -> Vec<(Point, Point, Point)> {
+        let radius = (self.u.0.hypot(self.u.1)).max(self.v.0.hypot(self.v.1));
+        let mut count = (self.sweep.abs() / (PI / 2.0)).ceil().max(1.0) as usize;
+        while count < MAXIMAL_CURVE_COUNT
+            && radius * error(self.sweep / count as Number) > tolerance
+        {
+            count *= 2;
+        }
+        let step = self.sweep / count as Number;
+        let factor = 4.0 / 3.0 * (step / 4.0).tan();
+        (0..count)
+            .map(|i| {
+                let (start, end) = (
+                    self.start + step * i as Number,
+                    self.start + step * (i + 1) as Number,
+                );
+                let (from, to) = (self.point(start), self.point(end));
+                let (one, two) = (self.tangent(start), self.tangent(end));
+                (
+                    (from.0 + factor * one.0, from.1 + factor * one.1),
+                    (to.0 - factor * two.0, to.1 - factor * two.1),
+                    to,
+                )
+            })
+            .collect()
+    }
+
+    fn tangent(&self, angle: Number) -> Point {
+        let (sin, cos) = angle.sin_cos();
+        (
+            -self.u.0 * sin + self.v.0 * cos,
+            -self.u.1 * sin + self.v.1 * cos,
+        )
+    }
+
+    /// Apply an affine transform.
+    pub fn transform(&self, matrix: &Matrix) -> Self {
+        let linear = |(x, y): Point| (matrix.a * x + matrix.c * y, matrix.b * x + matrix.d * y);
+        Arc {
+            center: matrix.apply(self.center),
+            u: linear(self.u),
+            v: linear(self.v),
+            ..*self
+        }
+    }
+}
+
+const MAXIMAL_CURVE_COUNT: usize = 1024;
+
+fn convert(
+    commands: &mut Vec<Command>,
+    from: Point,
+    arc: Option<Arc>,
+    to: Point,
+    tolerance: Number,
+) {
+    match arc {
+        Some(arc) => {
+            let mut curves = arc.cubic_curves(tolerance);
+            if let Some(last) = curves.last_mut() {
+                last.2 = to;
+            }
+            for (one, two, to) in curves {
+                let parameters = vec![one.0, one.1, two.0, two.1, to.0, to.1];
+                commands.push(Command::CubicCurve(Position::Absolute, parameters.into()));
+            }
+        }
+        _ if from != to => {
+            commands.push(Command::Line(Position::Absolute, vec![to.0, to.1].into()));
+        }
+        _ => {}
+    }
+}
+
+// The maximal radial deviation of a cubic Bézier curve from a unit circular
+// arc spanning a given angle.
+fn error(angle: Number) -> Number {
+    let quarter = angle.abs() / 4.0;
+    4.0 / 27.0 * quarter.sin().powi(6) / quarter.cos().powi(2)
+}
+
+fn angle((ux, uy): Point, (vx, vy): Point) -> Number {
+    (ux * vy - uy * vx).atan2(ux * vx + uy * vy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arc;
+    use crate::node::element::path::curve::evaluate_cubic;
+    use crate::node::element::path::{Command, Data, Position, Segment};
+    use crate::node::Value;
+
+    #[test]
+    fn arc_cubic_curves() {
+        let arc = Arc::new((1.0, 0.0), (1.0, 1.0), 0.0, true, true, (0.0, -1.0)).unwrap();
+        for tolerance in [1e-1, 1e-3, 1e-5] {
+            let curves = arc.cubic_curves(tolerance);
+            let mut from = arc.point(arc.start);
+            for &(one, two, to) in curves.iter() {
+                for i in 0..=10 {
+                    let (x, y) = evaluate_cubic(from, one, two, to, i as f32 / 10.0);
+                    assert!((x.hypot(y) - 1.0).abs() <= tolerance + 1e-6);
+                }
+                from = to;
+            }
+            assert_close!(from, (0.0, -1.0), 1e-5);
+        }
+        assert_eq!(arc.cubic_curves(1e-1).len(), 3);
+        assert_eq!(arc.cubic_curves(1e-5).len(), 6);
+    }
+
+    #[test]
+    fn command_arc_to_cubic_curves() {
+        let command = Command::EllipticalArc(
+            Position::Relative,
+            vec![
+                1.0, 1.0, 0.0, 0.0, 1.0, 2.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+            ]
+            .into(),
+        );
+        let commands = command.arc_to_cubic_curves((0.0, 0.0), 1e-2).unwrap();
+        assert_eq!(commands.len(), 3);
+        match commands[1] {
+            Command::CubicCurve(Position::Absolute, ref parameters) => {
+                assert_eq!(&parameters[4..], &[2.0, 0.0]);
+            }
+            _ => unreachable!(),
+        }
+        match commands[2] {
+            Command::Line(Position::Absolute, ref parameters) => {
+                assert_eq!(&parameters[..], &[3.0, 0.0]);
+            }
+            _ => unreachable!(),
+        }
+        assert!(Command::Close
+            .arc_to_cubic_curves((0.0, 0.0), 1e-2)
+            .is_none());
+    }
+
+    #[test]
+    fn data_arcs_to_cubic_curves() {
+        let data = Data::parse("M0 0 a1 1 0 0 1 2 0 s1 1 2 0 A1 1 0 0 1 4 0").unwrap();
+        let data = data.arcs_to_cubic_curves(1e-3);
+        assert!(data
+            .segments()
+            .all(|segment| matches!(segment, Segment::Cubic { .. })));
+        assert_eq!(
+            Value::from(data.arcs_to_cubic_curves(1e-3)).to_string(),
+            Value::from(data.clone()).to_string(),
+        );
+        let bounding_box = data.bounding_box().unwrap();
+        assert_close!(bounding_box.min, (0.0, -1.0), 1e-3);
+        assert_close!(bounding_box.max, (4.0, 4.0 / 9.0), 1e-3);
+    }
+}
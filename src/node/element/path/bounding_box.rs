@@ -0,0 +1,202 @@
+use std::f32::consts::PI;
+
+use super::arc::Arc;
+use super::curve::{evaluate_cubic, evaluate_quadratic};
+use super::{Data, Number, Point, Segment};
+use crate::node::value::{Matrix, ViewBox};
+
+/// A bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    /// The minimal coordinates.
+    pub min: Point,
+    /// The maximal coordinates.
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// Create a bounding box.
+    #[inline]
+    pub fn new(min: Point, max: Point) -> Self {
+        BoundingBox { min, max }
+    }
+
+    /// Return the width.
+    #[inline]
+    pub fn width(&self) -> Number {
+        self.max.0 - self.min.0
+    }
+
+    /// Return the height.
+    #[inline]
+    pub fn height(&self) -> Number {
+        self.max.1 - self.min.1
+    }
+
+    fn include(&mut self, (x, y): Point) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+}
+
+impl Data {
+    /// Compute the exact bounding box.
+    ///
+    /// `None` is returned if there are no segments.
+    #[inline]
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.bounding_box_with(&Matrix::identity())
+    }
+
+    /// Compute the exact bounding box after applying an affine transform.
+    ///
+    /// `None` is returned if there are no segments.
+    pub fn bounding_box_with(&self, matrix: &Matrix) -> Option<BoundingBox> {
+        let mut result: Option<BoundingBox> = None;
+        let mut include = |point: Point| match result.as_mut() {
+            Some(result) => result.include(point),
+            _ => result = Some(BoundingBox::new(point, point)),
+        };
+        for segment in self.segments() {
+            let (from, to) = (matrix.apply(segment.start()), matrix.apply(segment.end()));
+            include(from);
+            include(to);
+            match segment {
+                Segment::Line { .. } | Segment::ClosePath { .. } => {}
+                Segment::Quadratic { control, .. } => {
+                    let control = matrix.apply(control);
+                    for t in extrema_quadratic(from, control, to) {
+                        include(evaluate_quadratic(from, control, to, t));
+                    }
+                }
+                Segment::Cubic { c1, c2, .. } => {
+                    let (c1, c2) = (matrix.apply(c1), matrix.apply(c2));
+                    for t in extrema_cubic(from, c1, c2, to) {
+                        include(evaluate_cubic(from, c1, c2, to, t));
+                    }
+                }
+                Segment::Arc {
+                    from,
+                    radii,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => {
+                    let arc = match Arc::new(from, radii, rotation, large_arc, sweep, to) {
+                        Some(arc) => arc.transform(matrix),
+                        _ => continue,
+                    };
+                    for (a, b) in [(arc.u.0, arc.v.0), (arc.u.1, arc.v.1)] {
+                        let angle = b.atan2(a);
+                        for angle in [angle, angle + PI] {
+                            if arc.contains(angle) {
+                                include(arc.point(angle));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+impl From<BoundingBox> for ViewBox {
+    #[inline]
+    fn from(bounding_box: BoundingBox) -> Self {
+        ViewBox::new(
+            bounding_box.min.0,
+            bounding_box.min.1,
+            bounding_box.width(),
+            bounding_box.height(),
+        )
+    }
+}
+
+fn extrema_quadratic(p0: Point, p1: Point, p2: Point) -> impl Iterator<Item = Number> {
+    let solve = |p0: Number, p1: Number, p2: Number| {
+        let denominator = p0 - 2.0 * p1 + p2;
+        if denominator == 0.0 {
+            None
+        } else {
+            Some((p0 - p1) / denominator)
+        }
+    };
+    [solve(p0.0, p1.0, p2.0), solve(p0.1, p1.1, p2.1)]
+        .into_iter()
+        .flatten()
+        .filter(|t| *t > 0.0 && *t < 1.0)
+}
+
+fn extrema_cubic(p0: Point, p1: Point, p2: Point, p3: Point) -> impl Iterator<Item = Number> {
+    let solve = |p0: Number, p1: Number, p2: Number, p3: Number| {
+        let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+        let b = 2.0 * (p0 - 2.0 * p1 + p2);
+        let c = p1 - p0;
+        if a.abs() < 1e-6 {
+            if b == 0.0 {
+                return [None, None];
+            }
+            return [Some(-c / b), None];
+        }
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return [None, None];
+        }
+        let root = discriminant.sqrt();
+        [Some((-b + root) / (2.0 * a)), Some((-b - root) / (2.0 * a))]
+    };
+    solve(p0.0, p1.0, p2.0, p3.0)
+        .into_iter()
+        .chain(solve(p0.1, p1.1, p2.1, p3.1))
+        .flatten()
+        .filter(|t| *t > 0.0 && *t < 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::path::Data;
+    use crate::node::value::{Matrix, ViewBox};
+
+    #[test]
+    fn data_bounding_box() {
+        macro_rules! test(
+            ($content:expr, $min:expr, $max:expr) => ({
+                let bounding_box = Data::parse($content).unwrap().bounding_box().unwrap();
+                assert_close!(bounding_box.min, $min);
+                assert_close!(bounding_box.max, $max);
+            });
+        );
+
+        test!("M1 2 h3 v4 z", (1.0, 2.0), (4.0, 6.0));
+        test!("M0 0 Q1 2 2 0", (0.0, 0.0), (2.0, 1.0));
+        test!("M0 0 C0 1 1 1 1 0", (0.0, 0.0), (1.0, 0.75));
+        test!("M0 0 C2 0 -1 0 1 0", (0.0, 0.0), (1.0, 0.0));
+        test!("M0 0 C-1 0 2 0 1 0", (-0.20711, 0.0), (1.20711, 0.0));
+        test!("M0 0 A1 1 0 0 1 2 0", (0.0, -1.0), (2.0, 0.0));
+        test!("M0 0 A1 1 0 1 1 0 1", (0.0, -0.5), (1.86603, 1.5));
+        test!("M0 0 a2 1 90 0 0 0 4", (-1.0, 0.0), (0.0, 4.0));
+        test!("M0 0 A0 1 0 0 1 2 0", (0.0, 0.0), (2.0, 0.0));
+
+        assert_eq!(Data::parse("M1 1").unwrap().bounding_box(), None);
+    }
+
+    #[test]
+    fn data_bounding_box_with() {
+        let data = Data::parse("M0 0 A1 1 0 0 1 2 0").unwrap();
+        let matrix = Matrix::translate(10.0, 0.0) * Matrix::scale(2.0, 3.0);
+        let bounding_box = data.bounding_box_with(&matrix).unwrap();
+        assert_close!(bounding_box.min, (10.0, -3.0));
+        assert_close!(bounding_box.max, (14.0, 0.0));
+
+        let data = Data::parse("M-1 -1 h2 v2 h-2 z").unwrap();
+        let bounding_box = data.bounding_box_with(&Matrix::rotate(45.0)).unwrap();
+        assert_close!(bounding_box.min, (-2f32.sqrt(), -2f32.sqrt()));
+        assert_close!(bounding_box.max, (2f32.sqrt(), 2f32.sqrt()));
+        assert_eq!(
+            ViewBox::from(data.bounding_box().unwrap()),
+            ViewBox::new(-1.0, -1.0, 2.0, 2.0)
+        );
+    }
+}
@@ -73,6 +73,17 @@ macro_rules! implement {
                 }
             }
         }
+
+        impl Command {
+            pub(crate) fn letter(&self) -> char {
+                use self::Command::*;
+                use super::Position::*;
+                match self {
+                    $($command($position, _) => $letter.chars().next().unwrap(),)*
+                    Close => 'z',
+                }
+            }
+        }
     );
 }
 
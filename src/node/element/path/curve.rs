@@ -0,0 +1,18 @@
+use super::{Number, Point};
+
+pub(super) fn evaluate_quadratic(p0: Point, p1: Point, p2: Point, t: Number) -> Point {
+    let s = 1.0 - t;
+    (
+        s * s * p0.0 + 2.0 * s * t * p1.0 + t * t * p2.0,
+        s * s * p0.1 + 2.0 * s * t * p1.1 + t * t * p2.1,
+    )
+}
+
+pub(super) fn evaluate_cubic(p0: Point, p1: Point, p2: Point, p3: Point, t: Number) -> Point {
+    let s = 1.0 - t;
+    let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
@@ -0,0 +1,174 @@
+use super::arc::Arc;
+use super::curve::{evaluate_cubic, evaluate_quadratic};
+use super::{Command, Data, Number, Point};
+
+/// A subpath flattened into a polyline.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Flattened {
+    /// The points.
+    pub points: Vec<Point>,
+    /// The flag indicating that the last point is connected to the first one.
+    pub closed: bool,
+}
+
+const MAXIMAL_SEGMENT_COUNT: usize = 1024;
+
+impl Data {
+    /// Flatten into polylines, one per subpath, deviating from the curves by
+    /// at most a given tolerance.
+    pub fn flatten(&self, tolerance: Number) -> Vec<Flattened> {
+        let mut polylines: Vec<Flattened> = Vec::new();
+        let mut current = (0.0, 0.0);
+        for command in self.normalize().iter() {
+            if let Command::Move(_, parameters) = command {
+                current = (parameters[0], parameters[1]);
+                polylines.push(Flattened {
+                    points: vec![current],
+                    closed: false,
+                });
+                continue;
+            }
+            let polyline = match polylines.last_mut() {
+                Some(polyline) => polyline,
+                _ => unreachable!(),
+            };
+            let from = current;
+            match command {
+                Command::Line(_, parameters) => {
+                    current = (parameters[0], parameters[1]);
+                    polyline.points.push(current);
+                }
+                Command::QuadraticCurve(_, parameters) => {
+                    let control = (parameters[0], parameters[1]);
+                    current = (parameters[2], parameters[3]);
+                    let count = count(0.25 * deviation(from, control, current), tolerance);
+                    polyline.points.extend((1..count).map(|i| {
+                        evaluate_quadratic(from, control, current, i as Number / count as Number)
+                    }));
+                    polyline.points.push(current);
+                }
+                Command::CubicCurve(_, parameters) => {
+                    let (one, two) = (
+                        (parameters[0], parameters[1]),
+                        (parameters[2], parameters[3]),
+                    );
+                    current = (parameters[4], parameters[5]);
+                    let maximum = deviation(from, one, two).max(deviation(one, two, current));
+                    let count = count(0.75 * maximum, tolerance);
+                    polyline.points.extend((1..count).map(|i| {
+                        evaluate_cubic(from, one, two, current, i as Number / count as Number)
+                    }));
+                    polyline.points.push(current);
+                }
+                Command::EllipticalArc(_, parameters) => {
+                    current = (parameters[5], parameters[6]);
+                    let arc = Arc::new(
+                        from,
+                        (parameters[0], parameters[1]),
+                        parameters[2],
+                        parameters[3] != 0.0,
+                        parameters[4] != 0.0,
+                        current,
+                    );
+                    if let Some(arc) = arc {
+                        let radius = arc.u.0.hypot(arc.u.1).max(arc.v.0.hypot(arc.v.1));
+                        let count = count(radius * arc.sweep * arc.sweep / 8.0, tolerance);
+                        let step = arc.sweep / count as Number;
+                        polyline
+                            .points
+                            .extend((1..count).map(|i| arc.point(arc.start + step * i as Number)));
+                    }
+                    if from != current {
+                        polyline.points.push(current);
+                    }
+                }
+                Command::Close => {
+                    polyline.closed = true;
+                    current = polyline.points[0];
+                }
+                _ => unreachable!(),
+            }
+        }
+        polylines
+    }
+}
+
+// The number of uniform steps needed for a curve whose deviation from a
+// straight line decreases quadratically with the number of steps.
+fn count(deviation: Number, tolerance: Number) -> usize {
+    if tolerance <= 0.0 {
+        return MAXIMAL_SEGMENT_COUNT;
+    }
+    let count = (deviation / tolerance).sqrt().ceil();
+    if count.is_nan() {
+        return MAXIMAL_SEGMENT_COUNT;
+    }
+    (count as usize).clamp(1, MAXIMAL_SEGMENT_COUNT)
+}
+
+fn deviation(p0: Point, p1: Point, p2: Point) -> Number {
+    (p0.0 - 2.0 * p1.0 + p2.0).hypot(p0.1 - 2.0 * p1.1 + p2.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Flattened;
+    use crate::node::element::path::Data;
+
+    #[test]
+    fn data_flatten() {
+        let polylines = Data::parse("M0 0 h2 v2 z m5 5 l1 1 M9 9")
+            .unwrap()
+            .flatten(0.1);
+        assert_eq!(
+            polylines,
+            &[
+                Flattened {
+                    points: vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0)],
+                    closed: true,
+                },
+                Flattened {
+                    points: vec![(5.0, 5.0), (6.0, 6.0)],
+                    closed: false,
+                },
+                Flattened {
+                    points: vec![(9.0, 9.0)],
+                    closed: false,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn data_flatten_curves() {
+        macro_rules! test(
+            ($content:expr, $tolerance:expr, $project:expr) => ({
+                let polylines = Data::parse($content).unwrap().flatten($tolerance);
+                assert_eq!(polylines.len(), 1);
+                let points = &polylines[0].points;
+                for pair in points.windows(2) {
+                    let middle = ((pair[0].0 + pair[1].0) / 2.0, (pair[0].1 + pair[1].1) / 2.0);
+                    assert_close!(middle, $project(middle), $tolerance + 1e-5);
+                }
+                points.len()
+            });
+        );
+
+        let circle = |(x, y): (f32, f32)| {
+            let distance = (x - 1.0).hypot(y);
+            (1.0 + (x - 1.0) / distance, y / distance)
+        };
+        let coarse = test!("M0 0 A1 1 0 0 1 2 0", 1e-1, circle);
+        let fine = test!("M0 0 A1 1 0 0 1 2 0", 1e-3, circle);
+        assert!(coarse < fine);
+
+        let parabola = |(x, _): (f32, f32)| (x, x * (2.0 - x) / 2.0);
+        let count = test!("M0 0 Q1 1 2 0", 1e-2, parabola);
+        assert!(count > 2);
+        let count = test!("M0 0 C0.66667 0.66667 1.33333 0.66667 2 0", 1e-2, parabola);
+        assert!(count > 2);
+
+        let polylines = Data::parse("M0 0 A0 1 0 0 1 2 0").unwrap().flatten(1e-2);
+        assert_eq!(polylines[0].points, &[(0.0, 0.0), (2.0, 0.0)]);
+    }
+}
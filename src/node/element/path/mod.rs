@@ -1,16 +1,28 @@
 //! The path element.
 
+mod arc;
+mod bounding_box;
 mod command;
+mod curve;
 mod data;
+mod flatten;
+mod normalize;
 mod parameters;
+mod segment;
 
+pub use self::bounding_box::BoundingBox;
 pub use self::command::Command;
 pub use self::data::Data;
+pub use self::flatten::Flattened;
 pub use self::parameters::Parameters;
+pub use self::segment::{Segment, Segments};
 
 /// A number.
 pub type Number = f32;
 
+/// A point.
+pub type Point = (Number, Number);
+
 /// A positioning method.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Position {
@@ -0,0 +1,208 @@
+use super::{Command, Data, Number, Point, Position};
+
+#[derive(Default)]
+pub(super) struct State {
+    pub commands: Vec<Command>,
+    current: Point,
+    start: Point,
+    open: bool,
+    cubic: Option<Point>,
+    quadratic: Option<Point>,
+}
+
+impl Data {
+    /// Convert into absolute `Move`, `Line`, `QuadraticCurve`, `CubicCurve`,
+    /// `EllipticalArc`, and `Close` commands with one segment each.
+    ///
+    /// Each subpath starts with a `Move` command, and incomplete parameter
+    /// groups are ignored.
+    pub fn normalize(&self) -> Data {
+        let mut state = State::default();
+        for command in self.iter() {
+            state.process(command);
+        }
+        Data::from(state.commands)
+    }
+}
+
+impl State {
+    pub fn process(&mut self, command: &Command) {
+        use super::Command::*;
+
+        match command {
+            Move(position, parameters) => {
+                for (i, chunk) in parameters.chunks_exact(2).enumerate() {
+                    let point = self.resolve(*position, chunk[0], chunk[1]);
+                    if i == 0 {
+                        self.move_to(point);
+                    } else {
+                        self.line_to(point);
+                    }
+                }
+            }
+            Line(position, parameters) => {
+                for chunk in parameters.chunks_exact(2) {
+                    let point = self.resolve(*position, chunk[0], chunk[1]);
+                    self.line_to(point);
+                }
+            }
+            HorizontalLine(position, parameters) => {
+                for &x in parameters.iter() {
+                    let (x, _) = self.resolve(*position, x, 0.0);
+                    self.line_to((x, self.current.1));
+                }
+            }
+            VerticalLine(position, parameters) => {
+                for &y in parameters.iter() {
+                    let (_, y) = self.resolve(*position, 0.0, y);
+                    self.line_to((self.current.0, y));
+                }
+            }
+            QuadraticCurve(position, parameters) => {
+                for chunk in parameters.chunks_exact(4) {
+                    let control = self.resolve(*position, chunk[0], chunk[1]);
+                    let point = self.resolve(*position, chunk[2], chunk[3]);
+                    self.quadratic_curve_to(control, point);
+                }
+            }
+            SmoothQuadraticCurve(position, parameters) => {
+                for chunk in parameters.chunks_exact(2) {
+                    let control = self.reflect(self.quadratic);
+                    let point = self.resolve(*position, chunk[0], chunk[1]);
+                    self.quadratic_curve_to(control, point);
+                }
+            }
+            CubicCurve(position, parameters) => {
+                for chunk in parameters.chunks_exact(6) {
+                    let first = self.resolve(*position, chunk[0], chunk[1]);
+                    let second = self.resolve(*position, chunk[2], chunk[3]);
+                    let point = self.resolve(*position, chunk[4], chunk[5]);
+                    self.cubic_curve_to(first, second, point);
+                }
+            }
+            SmoothCubicCurve(position, parameters) => {
+                for chunk in parameters.chunks_exact(4) {
+                    let first = self.reflect(self.cubic);
+                    let second = self.resolve(*position, chunk[0], chunk[1]);
+                    let point = self.resolve(*position, chunk[2], chunk[3]);
+                    self.cubic_curve_to(first, second, point);
+                }
+            }
+            EllipticalArc(position, parameters) => {
+                for chunk in parameters.chunks_exact(7) {
+                    let point = self.resolve(*position, chunk[5], chunk[6]);
+                    self.begin();
+                    self.push(EllipticalArc(
+                        Position::Absolute,
+                        vec![
+                            chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], point.0, point.1,
+                        ]
+                        .into(),
+                    ));
+                    self.current = point;
+                }
+            }
+            Close => {
+                if self.open {
+                    self.commands.push(Close);
+                    self.current = self.start;
+                    self.open = false;
+                }
+                self.cubic = None;
+                self.quadratic = None;
+            }
+        }
+    }
+
+    fn resolve(&self, position: Position, x: Number, y: Number) -> Point {
+        match position {
+            Position::Absolute => (x, y),
+            Position::Relative => (self.current.0 + x, self.current.1 + y),
+        }
+    }
+
+    fn reflect(&self, control: Option<Point>) -> Point {
+        match control {
+            Some((x, y)) => (2.0 * self.current.0 - x, 2.0 * self.current.1 - y),
+            _ => self.current,
+        }
+    }
+
+    fn move_to(&mut self, point: Point) {
+        self.push(Command::Move(
+            Position::Absolute,
+            vec![point.0, point.1].into(),
+        ));
+        self.current = point;
+        self.start = point;
+        self.open = true;
+    }
+
+    fn line_to(&mut self, point: Point) {
+        self.begin();
+        self.push(Command::Line(
+            Position::Absolute,
+            vec![point.0, point.1].into(),
+        ));
+        self.current = point;
+    }
+
+    fn quadratic_curve_to(&mut self, control: Point, point: Point) {
+        self.begin();
+        let parameters = vec![control.0, control.1, point.0, point.1];
+        self.push(Command::QuadraticCurve(
+            Position::Absolute,
+            parameters.into(),
+        ));
+        self.current = point;
+        self.quadratic = Some(control);
+    }
+
+    fn cubic_curve_to(&mut self, first: Point, second: Point, point: Point) {
+        self.begin();
+        let parameters = vec![first.0, first.1, second.0, second.1, point.0, point.1];
+        self.push(Command::CubicCurve(Position::Absolute, parameters.into()));
+        self.current = point;
+        self.cubic = Some(second);
+    }
+
+    fn begin(&mut self) {
+        if !self.open {
+            self.move_to(self.current);
+        }
+    }
+
+    fn push(&mut self, command: Command) {
+        self.commands.push(command);
+        self.cubic = None;
+        self.quadratic = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::path::Data;
+    use crate::node::Value;
+
+    #[test]
+    fn data_normalize() {
+        macro_rules! test(
+            ($content:expr, $expected:expr) => (
+                assert_eq!(
+                    Value::from(Data::parse($content).unwrap().normalize()).to_string(),
+                    $expected,
+                );
+            );
+        );
+
+        test!("m1 2 3 4 h5 v-1 z l1 1", "M1,2 L4,6 L9,6 L9,5 z M1,2 L2,3");
+        test!(
+            "M0 0 C1 1 2 2 3 3 S5 5 6 6 Q7 7 8 8 T10 10 t1 1 a1 2 30 1 0 1 1",
+            "M0,0 C1,1,2,2,3,3 C4,4,5,5,6,6 Q7,7,8,8 Q9,9,10,10 Q11,11,11,11 \
+             A1,2,30,1,0,12,12"
+        );
+        test!("M1 1 S2 2 3 3 L4 4 T5 5", "M1,1 C1,1,2,2,3,3 L4,4 Q4,4,5,5");
+        test!("L1 2 3 z z", "M0,0 L1,2 z");
+        test!("M1 2 M3 4", "M1,2 M3,4");
+    }
+}
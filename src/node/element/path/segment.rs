@@ -0,0 +1,209 @@
+use std::{mem, slice, vec};
+
+use super::normalize::State;
+use super::{Command, Data, Number, Point};
+
+/// A segment of a data attribute with absolute coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Segment {
+    /// A straight line.
+    Line {
+        /// The start point.
+        from: Point,
+        /// The end point.
+        to: Point,
+    },
+    /// A quadratic Bézier curve.
+    Quadratic {
+        /// The start point.
+        from: Point,
+        /// The control point.
+        control: Point,
+        /// The end point.
+        to: Point,
+    },
+    /// A cubic Bézier curve.
+    Cubic {
+        /// The start point.
+        from: Point,
+        /// The first control point.
+        c1: Point,
+        /// The second control point.
+        c2: Point,
+        /// The end point.
+        to: Point,
+    },
+    /// An elliptical arc.
+    Arc {
+        /// The start point.
+        from: Point,
+        /// The radii.
+        radii: (Number, Number),
+        /// The rotation of the x axis of the ellipse in degrees.
+        rotation: Number,
+        /// The flag selecting the larger of the two possible arcs.
+        large_arc: bool,
+        /// The flag selecting the arc drawn in the positive-angle direction.
+        sweep: bool,
+        /// The end point.
+        to: Point,
+    },
+    /// A straight line closing the current subpath.
+    ClosePath {
+        /// The start point.
+        from: Point,
+        /// The end point, which is the start of the subpath.
+        to: Point,
+    },
+}
+
+/// An iterator over the segments of a data attribute.
+///
+/// The commands are normalized one at a time as the iterator advances.
+pub struct Segments<'l> {
+    commands: slice::Iter<'l, Command>,
+    state: State,
+    pending: vec::IntoIter<Command>,
+    current: Point,
+    start: Point,
+}
+
+impl Data {
+    /// Return an iterator over the segments.
+    pub fn segments(&self) -> Segments<'_> {
+        Segments {
+            commands: self.iter(),
+            state: State::default(),
+            pending: Vec::new().into_iter(),
+            current: (0.0, 0.0),
+            start: (0.0, 0.0),
+        }
+    }
+}
+
+impl Segment {
+    /// Return the start point.
+    pub fn start(&self) -> Point {
+        match *self {
+            Segment::Line { from, .. }
+            | Segment::Quadratic { from, .. }
+            | Segment::Cubic { from, .. }
+            | Segment::Arc { from, .. }
+            | Segment::ClosePath { from, .. } => from,
+        }
+    }
+
+    /// Return the end point.
+    pub fn end(&self) -> Point {
+        match *self {
+            Segment::Line { to, .. }
+            | Segment::Quadratic { to, .. }
+            | Segment::Cubic { to, .. }
+            | Segment::Arc { to, .. }
+            | Segment::ClosePath { to, .. } => to,
+        }
+    }
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let command = match self.pending.next() {
+                Some(command) => command,
+                _ => {
+                    self.state.process(self.commands.next()?);
+                    self.pending = mem::take(&mut self.state.commands).into_iter();
+                    continue;
+                }
+            };
+            let from = self.current;
+            let segment = match command {
+                Command::Move(_, parameters) => {
+                    self.current = (parameters[0], parameters[1]);
+                    self.start = self.current;
+                    continue;
+                }
+                Command::Line(_, parameters) => Segment::Line {
+                    from,
+                    to: (parameters[0], parameters[1]),
+                },
+                Command::QuadraticCurve(_, parameters) => Segment::Quadratic {
+                    from,
+                    control: (parameters[0], parameters[1]),
+                    to: (parameters[2], parameters[3]),
+                },
+                Command::CubicCurve(_, parameters) => Segment::Cubic {
+                    from,
+                    c1: (parameters[0], parameters[1]),
+                    c2: (parameters[2], parameters[3]),
+                    to: (parameters[4], parameters[5]),
+                },
+                Command::EllipticalArc(_, parameters) => Segment::Arc {
+                    from,
+                    radii: (parameters[0], parameters[1]),
+                    rotation: parameters[2],
+                    large_arc: parameters[3] != 0.0,
+                    sweep: parameters[4] != 0.0,
+                    to: (parameters[5], parameters[6]),
+                },
+                Command::Close => Segment::ClosePath {
+                    from,
+                    to: self.start,
+                },
+                _ => unreachable!(),
+            };
+            self.current = segment.end();
+            return Some(segment);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::path::{Data, Segment};
+
+    #[test]
+    fn data_segments() {
+        let data =
+            Data::parse("m1 1 h2 q1 1 2 0 c0 1 1 1 1 0 a1 2 30 1 0 -1 -1 z M0 0 l1 1").unwrap();
+        assert_eq!(
+            data.segments().collect::<Vec<_>>(),
+            &[
+                Segment::Line {
+                    from: (1.0, 1.0),
+                    to: (3.0, 1.0),
+                },
+                Segment::Quadratic {
+                    from: (3.0, 1.0),
+                    control: (4.0, 2.0),
+                    to: (5.0, 1.0),
+                },
+                Segment::Cubic {
+                    from: (5.0, 1.0),
+                    c1: (5.0, 2.0),
+                    c2: (6.0, 2.0),
+                    to: (6.0, 1.0),
+                },
+                Segment::Arc {
+                    from: (6.0, 1.0),
+                    radii: (1.0, 2.0),
+                    rotation: 30.0,
+                    large_arc: true,
+                    sweep: false,
+                    to: (5.0, 0.0),
+                },
+                Segment::ClosePath {
+                    from: (5.0, 0.0),
+                    to: (1.0, 1.0),
+                },
+                Segment::Line {
+                    from: (0.0, 0.0),
+                    to: (1.0, 1.0),
+                },
+            ],
+        );
+        assert_eq!(Data::parse("M1 1").unwrap().segments().count(), 0);
+    }
+}
@@ -2,8 +2,10 @@
 
 #![allow(non_upper_case_globals)]
 
+use std::borrow::Cow;
+
 use crate::node::Attributes;
-use crate::parser::{Error, Reader, Result};
+use crate::parser::{decode, Entities, Error, Reader, Result};
 
 /// A tag.
 #[derive(Clone, Debug)]
@@ -24,13 +26,39 @@ pub enum Type {
 
 struct Parser<'l> {
     reader: Reader<'l>,
+    sources: Vec<(&'l str, &'l str)>,
 }
 
 impl<'l> Tag<'l> {
     /// Parse a tag.
+    ///
+    /// Character references and predefined entity references in the values of
+    /// the attributes are decoded.
     #[inline]
     pub fn parse(content: &'l str) -> Result<Tag<'l>> {
-        Parser::new(content).process()
+        Tag::parse_with(content, &mut Entities::new()).map(|(tag, _)| tag)
+    }
+
+    pub(crate) fn parse_with(
+        content: &'l str,
+        entities: &mut Entities,
+    ) -> Result<(Tag<'l>, Vec<(&'l str, &'l str)>)> {
+        let mut parser = Parser::new(content);
+        let Tag(name, kind, mut attributes) = parser.process()?;
+        for value in attributes.values_mut() {
+            let decoded = match decode(value, entities) {
+                Some(Cow::Owned(decoded)) => decoded,
+                Some(_) => continue,
+                _ => {
+                    return Err(Error::new(
+                        parser.reader.position(),
+                        "found entities expanding beyond the limit",
+                    ))
+                }
+            };
+            *value = decoded.into();
+        }
+        Ok((Tag(name, kind, attributes), parser.sources))
     }
 }
 
@@ -45,6 +73,7 @@ impl<'l> Parser<'l> {
     fn new(content: &'l str) -> Self {
         Parser {
             reader: Reader::new(content),
+            sources: Vec::new(),
         }
     }
 
@@ -57,16 +86,14 @@ impl<'l> Parser<'l> {
     }
 
     fn read_attribute(&mut self) -> Result<Option<(String, String)>> {
-        let attribute = self
-            .reader
-            .capture(|reader| reader.consume_attribute())
-            .map(String::from);
+        let attribute = self.reader.capture(|reader| reader.consume_attribute());
         match attribute {
             Some(attribute) => {
                 let k = attribute.find('=').unwrap();
                 let name = attribute[0..k].trim_end();
                 let value = attribute[(k + 1)..].trim_start();
                 let value = &value[1..(value.len() - 1)];
+                self.sources.push((name, attribute));
                 Ok(Some((String::from(name), String::from(value))))
             }
             _ => Ok(None),
@@ -212,6 +239,13 @@ mod tests {
         test!("foo /", Empty);
     }
 
+    #[test]
+    fn tag_parse() {
+        let Tag(_, _, attributes) = Tag::parse("foo bar='&lt;&#x3E;' baz='&qux;'").unwrap();
+        assert_eq!(attributes["bar"], "<>");
+        assert_eq!(attributes["baz"], "&qux;");
+    }
+
     #[test]
     fn parser_read_attribute() {
         macro_rules! test(
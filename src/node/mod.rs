@@ -1,22 +1,28 @@
 //! The nodes.
 
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
 use std::fmt;
 
+use crate::writer::{Options, Writer};
+
+mod attributes;
 mod blob;
+mod cdata;
 mod comment;
-mod text;
-mod value;
+mod declaration;
+mod instruction;
+pub(crate) mod text;
+pub mod value;
 
+pub use self::attributes::Attributes;
 pub use self::blob::Blob;
+pub use self::cdata::CData;
 pub use self::comment::Comment;
+pub use self::declaration::Declaration;
+pub use self::instruction::Instruction;
 pub use self::text::Text;
 pub use self::value::Value;
 
-/// Attributes.
-pub type Attributes = HashMap<String, Value>;
-
 /// Child nodes.
 pub type Children = Vec<Box<dyn Node>>;
 
@@ -67,6 +73,19 @@ pub trait Node:
         None
     }
 
+    /// Convert into a string according to options.
+    fn to_string_with(&self, options: &Options) -> String {
+        let mut content = String::new();
+        self.serialize(&mut Writer::new(&mut content, options))
+            .expect("failed to serialize a node");
+        content
+    }
+
+    #[doc(hidden)]
+    fn serialize(&self, writer: &mut Writer) -> fmt::Result {
+        writer.display(self, self.is_bare())
+    }
+
     #[doc(hidden)]
     fn is_bare(&self) -> bool {
         false
@@ -123,3 +142,4 @@ impl NodeDefaultHash for Box<dyn Node> {
 }
 
 pub mod element;
+pub mod namespace;
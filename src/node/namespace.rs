@@ -0,0 +1,234 @@
+//! The namespaces.
+
+use crate::node::{Attributes, Node, Value};
+
+/// The [SVG](https://www.w3.org/TR/SVG/) namespace.
+pub const SVG: &str = "http://www.w3.org/2000/svg";
+
+/// The [XHTML](https://www.w3.org/TR/xhtml1/) namespace.
+pub const XHTML: &str = "http://www.w3.org/1999/xhtml";
+
+/// The [XLink](https://www.w3.org/TR/xlink11/) namespace.
+pub const XLINK: &str = "http://www.w3.org/1999/xlink";
+
+/// The [XML](https://www.w3.org/TR/xml-names/#ns-decl) namespace.
+pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The [XMLNS](https://www.w3.org/TR/xml-names/#ns-decl) namespace.
+pub const XMLNS: &str = "http://www.w3.org/2000/xmlns/";
+
+/// A qualified name.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Name<'l> {
+    /// The namespace.
+    pub namespace: Option<&'l str>,
+    /// The local name.
+    pub local: &'l str,
+}
+
+/// A scope of namespace declarations.
+#[derive(Clone, Debug, Default)]
+pub struct Scope {
+    bindings: Vec<(String, String)>,
+    marks: Vec<usize>,
+}
+
+impl Scope {
+    /// Create a scope.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Enter an element with the given attributes.
+    pub fn push(&mut self, attributes: &Attributes) {
+        self.marks.push(self.bindings.len());
+        for (name, value) in attributes.iter() {
+            let prefix = match split(name) {
+                (None, "xmlns") => "",
+                (Some("xmlns"), prefix) => prefix,
+                _ => continue,
+            };
+            self.bindings.push((prefix.into(), value.to_string()));
+        }
+    }
+
+    /// Leave the most recently entered element.
+    pub fn pop(&mut self) {
+        if let Some(mark) = self.marks.pop() {
+            self.bindings.truncate(mark);
+        }
+    }
+
+    /// Return the namespace bound to a prefix or the default namespace.
+    pub fn lookup(&self, prefix: Option<&str>) -> Option<&str> {
+        match prefix {
+            Some("xml") => return Some(XML),
+            Some("xmlns") => return Some(XMLNS),
+            _ => {}
+        }
+        let prefix = prefix.unwrap_or("");
+        self.bindings
+            .iter()
+            .rev()
+            .find(|(other, _)| other == prefix)
+            .map(|(_, namespace)| namespace.as_str())
+            .filter(|namespace| !namespace.is_empty())
+    }
+
+    /// Resolve the name of an element.
+    ///
+    /// Unprefixed names belong to the default namespace. `None` is returned
+    /// if the prefix is not bound.
+    pub fn resolve_element<'l>(&'l self, name: &'l str) -> Option<Name<'l>> {
+        let (prefix, local) = split(name);
+        let namespace = self.lookup(prefix);
+        if prefix.is_some() && namespace.is_none() {
+            return None;
+        }
+        Some(Name { namespace, local })
+    }
+
+    /// Resolve the name of an attribute.
+    ///
+    /// Unprefixed names belong to no namespace, except for `xmlns` itself.
+    /// `None` is returned if the prefix is not bound.
+    pub fn resolve_attribute<'l>(&'l self, name: &'l str) -> Option<Name<'l>> {
+        match split(name) {
+            (None, "xmlns") => Some(Name {
+                namespace: Some(XMLNS),
+                local: "xmlns",
+            }),
+            (None, local) => Some(Name {
+                namespace: None,
+                local,
+            }),
+            (prefix, local) => self.lookup(prefix).map(|namespace| Name {
+                namespace: Some(namespace),
+                local,
+            }),
+        }
+    }
+
+    /// Find an attribute by its qualified name.
+    ///
+    /// The attributes are expected to have already been pushed.
+    pub fn find<'l>(
+        &self,
+        attributes: &'l Attributes,
+        namespace: Option<&str>,
+        local: &str,
+    ) -> Option<&'l Value> {
+        attributes
+            .iter()
+            .find(|(name, _)| {
+                self.resolve_attribute(name)
+                    .is_some_and(|name| name.namespace == namespace && name.local == local)
+            })
+            .map(|(_, value)| value)
+    }
+}
+
+/// Split a name into a prefix and a local name.
+pub fn split(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        _ => (None, name),
+    }
+}
+
+pub(crate) fn remove(node: &mut dyn Node, scope: &mut Scope, namespace: &str) {
+    if let Some(attributes) = node.get_attributes_mut() {
+        let names = attributes
+            .iter()
+            .filter(|(name, value)| match scope.resolve_attribute(name) {
+                Some(Name {
+                    namespace: Some(XMLNS),
+                    ..
+                }) => **value == namespace,
+                Some(name) => name.namespace == Some(namespace),
+                _ => false,
+            })
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        for name in names {
+            attributes.remove(&name);
+        }
+    }
+    if let Some(children) = node.get_children_mut() {
+        children.retain_mut(|child| {
+            let attributes = match child.get_attributes() {
+                Some(attributes) => attributes,
+                _ => return true,
+            };
+            scope.push(attributes);
+            let name = scope.resolve_element(child.get_name());
+            let keep = name.is_none_or(|name| name.namespace != Some(namespace));
+            if keep {
+                remove(&mut **child, scope, namespace);
+            }
+            scope.pop();
+            keep
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split, Name, Scope, XLINK, XML};
+    use crate::node::Attributes;
+
+    #[test]
+    fn scope_resolve() {
+        let mut scope = Scope::new();
+        let mut attributes = Attributes::new();
+        attributes.insert("xmlns".into(), "foo".into());
+        attributes.insert("xmlns:xlink".into(), XLINK.into());
+        attributes.insert("xlink:href".into(), "#bar".into());
+        scope.push(&attributes);
+
+        let mut attributes = Attributes::new();
+        attributes.insert("xmlns".into(), "".into());
+        attributes.insert("xmlns:xlink".into(), "baz".into());
+        attributes.insert("href".into(), "#qux".into());
+
+        assert_eq!(
+            scope.resolve_element("svg"),
+            Some(Name {
+                namespace: Some("foo"),
+                local: "svg",
+            }),
+        );
+        assert_eq!(
+            scope.resolve_attribute("xlink:href"),
+            Some(Name {
+                namespace: Some(XLINK),
+                local: "href",
+            }),
+        );
+        assert_eq!(
+            scope.resolve_attribute("xml:space"),
+            Some(Name {
+                namespace: Some(XML),
+                local: "space",
+            }),
+        );
+        assert_eq!(scope.resolve_element("foo:bar"), None);
+
+        scope.push(&attributes);
+        assert_eq!(scope.lookup(None), None);
+        assert_eq!(scope.lookup(Some("xlink")), Some("baz"));
+        assert!(scope.find(&attributes, Some(XLINK), "href").is_none());
+        assert_eq!(&**scope.find(&attributes, None, "href").unwrap(), "#qux");
+
+        scope.pop();
+        assert_eq!(scope.lookup(None), Some("foo"));
+        assert_eq!(scope.lookup(Some("xlink")), Some(XLINK));
+    }
+
+    #[test]
+    fn split_name() {
+        assert_eq!(split("xlink:href"), (Some("xlink"), "href"));
+        assert_eq!(split("href"), (None, "href"));
+    }
+}
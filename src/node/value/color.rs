@@ -0,0 +1,485 @@
+//! The colors.
+
+use std::fmt;
+
+use super::Number;
+use crate::node::Value;
+use crate::parser::{Error, Reader, Result};
+
+/// A [color][1].
+///
+/// [1]: https://www.w3.org/TR/css-color-3/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Color {
+    /// The red component.
+    pub red: u8,
+    /// The green component.
+    pub green: u8,
+    /// The blue component.
+    pub blue: u8,
+    /// The alpha component.
+    pub alpha: u8,
+}
+
+struct Parser<'l> {
+    reader: Reader<'l>,
+}
+
+impl Color {
+    /// Create an opaque color.
+    #[inline]
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Color::rgba(red, green, blue, 255)
+    }
+
+    /// Create a color.
+    #[inline]
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Color {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Parse a color.
+    ///
+    /// Hexadecimal notations, the `rgb`, `rgba`, `hsl`, and `hsla` functions,
+    /// and named colors are supported. Keywords depending on the context, such
+    /// as `currentColor`, are not colors; see `Paint`.
+    #[inline]
+    pub fn parse(content: &str) -> Result<Self> {
+        Parser::new(content).process()
+    }
+
+    /// Return the red, green, blue, and alpha components.
+    #[inline]
+    pub fn to_rgba(&self) -> [u8; 4] {
+        [self.red, self.green, self.blue, self.alpha]
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let components = &self.to_rgba()[..if self.alpha == 255 { 3 } else { 4 }];
+        let short = components.iter().all(|value| value >> 4 == value & 0xf);
+        let mut hex = String::from("#");
+        for value in components {
+            if short {
+                hex.push_str(&format!("{:x}", value & 0xf));
+            } else {
+                hex.push_str(&format!("{:02x}", value));
+            }
+        }
+        if self.alpha == 255 {
+            let name = NAMES
+                .iter()
+                .filter(|(_, value)| value == components)
+                .map(|(name, _)| *name)
+                .min_by_key(|name| name.len());
+            if let Some(name) = name.filter(|name| name.len() < hex.len()) {
+                return formatter.write_str(name);
+            }
+        }
+        formatter.write_str(&hex)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    #[inline]
+    fn from((red, green, blue): (u8, u8, u8)) -> Self {
+        Color::rgb(red, green, blue)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Color {
+    #[inline]
+    fn from((red, green, blue, alpha): (u8, u8, u8, u8)) -> Self {
+        Color::rgba(red, green, blue, alpha)
+    }
+}
+
+impl From<Color> for Value {
+    #[inline]
+    fn from(color: Color) -> Self {
+        color.to_string().into()
+    }
+}
+
+macro_rules! raise(
+    ($parser:expr, $($argument:tt)*) => (
+        return Err(Error::new($parser.reader.position(), format!($($argument)*)))
+    );
+);
+
+impl<'l> Parser<'l> {
+    #[inline]
+    fn new(content: &'l str) -> Self {
+        Parser {
+            reader: Reader::new(content),
+        }
+    }
+
+    fn process(&mut self) -> Result<Color> {
+        self.reader.consume_whitespace();
+        let color = if self.reader.consume_char('#') {
+            self.read_hex()?
+        } else {
+            self.read_name_or_function()?
+        };
+        self.reader.consume_whitespace();
+        if !self.reader.is_done() {
+            raise!(self, "found an unexpected ending of a color");
+        }
+        Ok(color)
+    }
+
+    fn read_arguments(&mut self) -> Result<Vec<(Number, &'l str)>> {
+        let mut arguments = Vec::new();
+        loop {
+            self.reader.consume_whitespace();
+            if self.reader.consume_char(')') {
+                return Ok(arguments);
+            }
+            let number = match self.reader.capture(|reader| reader.consume_number()) {
+                Some(number) => number,
+                _ => raise!(self, "expected a number"),
+            };
+            let number = match number.parse() {
+                Ok(number) => number,
+                _ => raise!(self, "failed to parse a number '{}'", number),
+            };
+            let unit = self
+                .reader
+                .capture(|reader| reader.consume_while(|c| c.is_ascii_alphabetic() || c == '%'))
+                .unwrap_or("");
+            arguments.push((number, unit));
+            self.reader.consume_whitespace();
+            if !self.reader.consume_char(',') {
+                self.reader.consume_char('/');
+            }
+        }
+    }
+
+    fn read_hex(&mut self) -> Result<Color> {
+        let digits = match self.reader.capture(|reader| reader.consume_digits_hex()) {
+            Some(digits) => digits,
+            _ => raise!(self, "expected hexadecimal digits"),
+        };
+        let (size, scale) = match digits.len() {
+            3 | 4 => (1, 17),
+            6 | 8 => (2, 1),
+            _ => raise!(self, "found an invalid hexadecimal color '#{}'", digits),
+        };
+        let mut components = [255; 4];
+        for (i, component) in components.iter_mut().enumerate() {
+            if let Some(value) = digits.get((i * size)..((i + 1) * size)) {
+                *component = u8::from_str_radix(value, 16).unwrap() * scale;
+            }
+        }
+        let [red, green, blue, alpha] = components;
+        Ok(Color::rgba(red, green, blue, alpha))
+    }
+
+    fn read_hsl(&mut self, arguments: &[(Number, &str)]) -> Result<Color> {
+        let hue = match arguments[0] {
+            (value, "" | "deg") => value,
+            (value, "rad") => value.to_degrees(),
+            (value, "grad") => value * 0.9,
+            (value, "turn") => value * 360.0,
+            (_, unit) => raise!(self, "found an unknown unit of a hue '{}'", unit),
+        };
+        let mut fractions = [0.0; 2];
+        for (fraction, argument) in fractions.iter_mut().zip(&arguments[1..3]) {
+            *fraction = match argument {
+                (value, "" | "%") => (value / 100.0).clamp(0.0, 1.0),
+                (_, unit) => raise!(self, "found an unexpected unit '{}'", unit),
+            };
+        }
+        let [saturation, lightness] = fractions;
+        let q = if lightness < 0.5 {
+            lightness * (1.0 + saturation)
+        } else {
+            lightness + saturation - lightness * saturation
+        };
+        let p = 2.0 * lightness - q;
+        let convert = |t: Number| {
+            let t = t - t.floor();
+            let value = if t < 1.0 / 6.0 {
+                p + (q - p) * 6.0 * t
+            } else if t < 0.5 {
+                q
+            } else if t < 2.0 / 3.0 {
+                p + (q - p) * (2.0 / 3.0 - t) * 6.0
+            } else {
+                p
+            };
+            scale(value)
+        };
+        let hue = hue / 360.0;
+        let alpha = self.read_alpha(arguments.get(3))?;
+        Ok(Color::rgba(
+            convert(hue + 1.0 / 3.0),
+            convert(hue),
+            convert(hue - 1.0 / 3.0),
+            alpha,
+        ))
+    }
+
+    fn read_alpha(&mut self, argument: Option<&(Number, &str)>) -> Result<u8> {
+        match argument {
+            Some(&(value, "")) => Ok(scale(value)),
+            Some(&(value, "%")) => Ok(scale(value / 100.0)),
+            Some((_, unit)) => raise!(self, "found an unexpected unit '{}'", unit),
+            _ => Ok(255),
+        }
+    }
+
+    fn read_name_or_function(&mut self) -> Result<Color> {
+        let name = self
+            .reader
+            .capture(|reader| reader.consume_while(|c| c.is_ascii_alphabetic()));
+        let name = match name {
+            Some(name) => name.to_ascii_lowercase(),
+            _ => raise!(self, "expected a color"),
+        };
+        if !self.reader.consume_char('(') {
+            return match NAMES.binary_search_by_key(&name.as_str(), |(name, _)| name) {
+                Ok(index) => {
+                    let [red, green, blue] = NAMES[index].1;
+                    Ok(Color::rgb(red, green, blue))
+                }
+                _ if name == "transparent" => Ok(Color::rgba(0, 0, 0, 0)),
+                _ => raise!(self, "found an unknown color '{}'", name),
+            };
+        }
+        let arguments = self.read_arguments()?;
+        if arguments.len() != 3 && arguments.len() != 4 {
+            raise!(self, "expected three or four arguments");
+        }
+        match name.as_str() {
+            "rgb" | "rgba" => self.read_rgb(&arguments),
+            "hsl" | "hsla" => self.read_hsl(&arguments),
+            _ => raise!(self, "found an unknown color function '{}'", name),
+        }
+    }
+
+    fn read_rgb(&mut self, arguments: &[(Number, &str)]) -> Result<Color> {
+        let mut components = [0; 3];
+        for (component, argument) in components.iter_mut().zip(arguments) {
+            *component = match argument {
+                (value, "") => scale(value / 255.0),
+                (value, "%") => scale(value / 100.0),
+                (_, unit) => raise!(self, "found an unexpected unit '{}'", unit),
+            };
+        }
+        let [red, green, blue] = components;
+        let alpha = self.read_alpha(arguments.get(3))?;
+        Ok(Color::rgba(red, green, blue, alpha))
+    }
+}
+
+#[inline]
+fn scale(value: Number) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// https://www.w3.org/TR/SVG11/types.html#ColorKeywords
+const NAMES: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, NAMES};
+
+    #[test]
+    fn color_display() {
+        macro_rules! test(
+            ($color:expr, $content:expr) => (assert_eq!(Color::from($color).to_string(), $content));
+        );
+
+        test!((255, 0, 0), "red");
+        test!((0, 255, 255), "#0ff");
+        test!((255, 120, 0), "#ff7800");
+        test!((255, 120, 0, 136), "#ff780088");
+        test!((255, 255, 255, 0), "#fff0");
+        test!((210, 180, 140), "tan");
+    }
+
+    #[test]
+    fn color_parse() {
+        macro_rules! test(
+            ($content:expr, $color:expr) => (
+                assert_eq!(Color::parse($content).unwrap(), Color::from($color));
+            );
+        );
+
+        test!("#FF780088", (255, 120, 0, 136));
+        test!(" #f80 ", (255, 136, 0));
+        test!("#f808", (255, 136, 0, 136));
+        test!("rgb(255, 120, 0)", (255, 120, 0));
+        test!("rgba(100%,0%,0%,0.5)", (255, 0, 0, 128));
+        test!("rgb(0 0 255 / 50%)", (0, 0, 255, 128));
+        test!("hsl(120, 100%, 25%)", (0, 128, 0));
+        test!("hsla(0.5turn 100% 50% / 1)", (0, 255, 255));
+        test!("CornflowerBlue", (100, 149, 237));
+        test!("transparent", (0, 0, 0, 0));
+
+        assert!(Color::parse("#ff78").is_ok());
+        assert!(Color::parse("#ff7").is_ok());
+        assert!(Color::parse("#ff78a").is_err());
+        assert!(Color::parse("currentColor").is_err());
+        assert!(Color::parse("rgb(1, 2)").is_err());
+        assert!(Color::parse("rgb(1px, 2, 3)").is_err());
+        assert!(Color::parse("red blue").is_err());
+        assert_eq!(NAMES.len(), 147);
+    }
+}
@@ -0,0 +1,195 @@
+//! The lengths.
+
+use std::fmt;
+
+use super::Number;
+use crate::node::Value;
+use crate::parser::{Error, Reader, Result};
+
+/// A [length][1].
+///
+/// [1]: https://www.w3.org/TR/SVG/types.html#InterfaceSVGLength
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length {
+    /// The number.
+    pub number: Number,
+    /// The unit.
+    pub unit: Unit,
+}
+
+macro_rules! implement {
+    ($($(#[$doc:meta])* $variant:ident => $suffix:expr,)*) => (
+        /// A unit of a length.
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum Unit {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl Unit {
+            /// Return the suffix.
+            pub fn suffix(&self) -> &'static str {
+                match self {
+                    $(Unit::$variant => $suffix,)*
+                }
+            }
+
+            fn parse(content: &str) -> Option<Self> {
+                $(if content.eq_ignore_ascii_case($suffix) {
+                    return Some(Unit::$variant);
+                })*
+                None
+            }
+        }
+    );
+}
+
+implement! {
+    /// No unit, that is, user units.
+    None => "",
+    /// Pixels.
+    Pixel => "px",
+    /// The font size.
+    Em => "em",
+    /// The x-height of the font.
+    Ex => "ex",
+    /// Millimeters.
+    Millimeter => "mm",
+    /// Centimeters.
+    Centimeter => "cm",
+    /// Inches.
+    Inch => "in",
+    /// Points.
+    Point => "pt",
+    /// Picas.
+    Pica => "pc",
+    /// A percentage of a reference length.
+    Percent => "%",
+}
+
+impl Unit {
+    fn pixels(&self, dpi: Number) -> Option<Number> {
+        match self {
+            Unit::None | Unit::Pixel => Some(1.0),
+            Unit::Em | Unit::Ex | Unit::Percent => None,
+            Unit::Millimeter => Some(dpi / 25.4),
+            Unit::Centimeter => Some(dpi / 2.54),
+            Unit::Inch => Some(dpi),
+            Unit::Point => Some(dpi / 72.0),
+            Unit::Pica => Some(dpi / 6.0),
+        }
+    }
+}
+
+impl Length {
+    /// Create a length.
+    #[inline]
+    pub fn new(number: Number, unit: Unit) -> Self {
+        Length { number, unit }
+    }
+
+    /// Parse a length.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::new(content);
+        reader.consume_whitespace();
+        let number = match reader.capture(|reader| reader.consume_number()) {
+            Some(number) => number,
+            _ => return Err(Error::new(reader.position(), "expected a number")),
+        };
+        let number = match number.parse() {
+            Ok(number) => number,
+            _ => {
+                let message = format!("failed to parse a number '{}'", number);
+                return Err(Error::new(reader.position(), message));
+            }
+        };
+        let unit = reader
+            .capture_exact(|reader| reader.consume_all())
+            .map_or("", str::trim_end);
+        match Unit::parse(unit) {
+            Some(unit) => Ok(Length { number, unit }),
+            _ => {
+                let message = format!("found an unknown unit '{}'", unit);
+                Err(Error::new(reader.position(), message))
+            }
+        }
+    }
+
+    /// Convert into pixels at a resolution given in dots per inch.
+    ///
+    /// `None` is returned for relative units, which require a context to be
+    /// resolved.
+    #[inline]
+    pub fn to_pixels(&self, dpi: Number) -> Option<Number> {
+        self.unit.pixels(dpi).map(|factor| self.number * factor)
+    }
+
+    /// Convert into another unit at a resolution given in dots per inch.
+    ///
+    /// `None` is returned if either unit is relative and the units differ.
+    pub fn convert(&self, unit: Unit, dpi: Number) -> Option<Self> {
+        if self.unit == unit {
+            return Some(*self);
+        }
+        let number = self.to_pixels(dpi)? / unit.pixels(dpi)?;
+        Some(Length { number, unit })
+    }
+}
+
+impl fmt::Display for Length {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}{}", self.number, self.unit.suffix())
+    }
+}
+
+impl From<Number> for Length {
+    #[inline]
+    fn from(number: Number) -> Self {
+        Length::new(number, Unit::None)
+    }
+}
+
+impl From<Length> for Value {
+    #[inline]
+    fn from(length: Length) -> Self {
+        length.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Length, Unit};
+
+    #[test]
+    fn length_parse() {
+        macro_rules! test(
+            ($content:expr, $number:expr, $unit:ident) => (
+                assert_eq!(Length::parse($content).unwrap(), Length::new($number, Unit::$unit));
+            );
+        );
+
+        test!("10", 10.0, None);
+        test!(" 10mm ", 10.0, Millimeter);
+        test!("-1.5em", -1.5, Em);
+        test!("2e1PX", 20.0, Pixel);
+        test!("50%", 50.0, Percent);
+
+        assert!(Length::parse("").is_err());
+        assert!(Length::parse("px").is_err());
+        assert!(Length::parse("10 px").is_err());
+        assert!(Length::parse("10qq").is_err());
+    }
+
+    #[test]
+    fn length_convert() {
+        let length = Length::new(1.0, Unit::Inch);
+        assert_eq!(length.to_pixels(96.0), Some(96.0));
+        assert_eq!(
+            length.convert(Unit::Point, 96.0),
+            Some(Length::new(72.0, Unit::Point))
+        );
+        assert_eq!(length.convert(Unit::Em, 96.0), None);
+        assert_eq!(Length::new(50.0, Unit::Percent).to_pixels(96.0), None);
+        assert_eq!(Length::new(2.5, Unit::Centimeter).to_string(), "2.5cm");
+    }
+}
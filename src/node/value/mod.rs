@@ -1,8 +1,29 @@
+//! The values.
+
 use std::fmt;
 use std::ops::Deref;
 
+mod color;
+mod length;
+mod paint;
+mod style;
+mod transform;
+mod view_box;
+
+pub use self::color::Color;
+pub use self::length::{Length, Unit};
+pub use self::paint::Paint;
+pub use self::style::Style;
+
+pub(crate) use self::style::{is_presentation_attribute, split_important};
+pub use self::transform::{Matrix, Transform, TransformFunction};
+pub use self::view_box::{Align, MeetOrSlice, PreserveAspectRatio, ViewBox};
+
+/// A number.
+pub type Number = f32;
+
 /// A value of an attribute.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Value(String);
 
 impl Deref for Value {
@@ -0,0 +1,128 @@
+//! The paints.
+
+use std::fmt;
+
+use super::Color;
+use crate::node::Value;
+use crate::parser::{Error, Reader, Result};
+
+/// A [paint][1] used for filling and stroking.
+///
+/// [1]: https://www.w3.org/TR/SVG/painting.html#SpecifyingPaint
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Paint {
+    /// No paint.
+    None,
+    /// The value of the `color` property.
+    CurrentColor,
+    /// A color.
+    Color(Color),
+    /// A reference to a paint server with an optional fallback used when the
+    /// reference is invalid.
+    Url(String, Option<Box<Paint>>),
+}
+
+impl Paint {
+    /// Parse a paint.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::new(content);
+        reader.consume_whitespace();
+        let rest = reader.peek_many().as_str();
+        if !rest
+            .get(..4)
+            .is_some_and(|name| name.eq_ignore_ascii_case("url("))
+        {
+            return Paint::parse_plain(rest);
+        }
+        for _ in 0..4 {
+            reader.next();
+        }
+        let url = reader
+            .capture(|reader| reader.consume_until_char(')'))
+            .map(|url| url.trim_matches(['"', '\'']));
+        let url = match url {
+            Some(url) if reader.consume_char(')') => url,
+            _ => return Err(Error::new(reader.position(), "expected a url")),
+        };
+        let position = reader.position();
+        let fallback = match reader.capture(|reader| reader.consume_all()) {
+            Some(fallback) => match Paint::parse_plain(fallback) {
+                Ok(fallback) => Some(Box::new(fallback)),
+                _ => {
+                    let message = format!("found an invalid fallback '{}'", fallback);
+                    return Err(Error::new(position, message));
+                }
+            },
+            _ => None,
+        };
+        Ok(Paint::Url(url.into(), fallback))
+    }
+
+    fn parse_plain(content: &str) -> Result<Self> {
+        match content.trim() {
+            content if content.eq_ignore_ascii_case("none") => Ok(Paint::None),
+            content if content.eq_ignore_ascii_case("currentColor") => Ok(Paint::CurrentColor),
+            content => Color::parse(content).map(Paint::Color),
+        }
+    }
+}
+
+impl fmt::Display for Paint {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Paint::None => formatter.write_str("none"),
+            Paint::CurrentColor => formatter.write_str("currentColor"),
+            Paint::Color(color) => color.fmt(formatter),
+            Paint::Url(url, None) => write!(formatter, "url({})", url),
+            Paint::Url(url, Some(fallback)) => write!(formatter, "url({}) {}", url, fallback),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    #[inline]
+    fn from(color: Color) -> Self {
+        Paint::Color(color)
+    }
+}
+
+impl From<Paint> for Value {
+    #[inline]
+    fn from(paint: Paint) -> Self {
+        paint.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Paint;
+    use crate::node::value::Color;
+
+    #[test]
+    fn paint_parse() {
+        macro_rules! test(
+            ($content:expr, $paint:expr, $display:expr) => ({
+                let paint = Paint::parse($content).unwrap();
+                assert_eq!(paint, $paint);
+                assert_eq!(paint.to_string(), $display);
+            });
+        );
+
+        test!("none", Paint::None, "none");
+        test!(" currentcolor", Paint::CurrentColor, "currentColor");
+        test!("#FF0000", Paint::Color(Color::rgb(255, 0, 0)), "red");
+        test!("url(#foo)", Paint::Url("#foo".into(), None), "url(#foo)");
+        test!(
+            "URL('#foo') rgb(0, 0, 255)",
+            Paint::Url(
+                "#foo".into(),
+                Some(Box::new(Paint::Color(Color::rgb(0, 0, 255))))
+            ),
+            "url(#foo) #00f"
+        );
+
+        assert!(Paint::parse("url(#foo").is_err());
+        assert!(Paint::parse("url(#foo) url(#bar)").is_err());
+        assert!(Paint::parse("foo").is_err());
+    }
+}
@@ -0,0 +1,414 @@
+//! The styles.
+
+use std::fmt;
+
+use crate::node::Value;
+use crate::parser::{Error, Reader, Result};
+
+/// A list of [declarations][1] given in a `style` attribute.
+///
+/// [1]: https://www.w3.org/TR/SVG11/styling.html#StyleAttribute
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    declarations: Vec<(String, String)>,
+}
+
+/// The [presentation attributes][1].
+///
+/// [1]: https://www.w3.org/TR/SVG11/propidx.html
+const PRESENTATION_ATTRIBUTES: &[&str] = &[
+    "alignment-baseline",
+    "baseline-shift",
+    "clip",
+    "clip-path",
+    "clip-rule",
+    "color",
+    "color-interpolation",
+    "color-interpolation-filters",
+    "color-profile",
+    "color-rendering",
+    "cursor",
+    "direction",
+    "display",
+    "dominant-baseline",
+    "enable-background",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "flood-color",
+    "flood-opacity",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "glyph-orientation-horizontal",
+    "glyph-orientation-vertical",
+    "image-rendering",
+    "kerning",
+    "letter-spacing",
+    "lighting-color",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "mask",
+    "opacity",
+    "overflow",
+    "pointer-events",
+    "shape-rendering",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "text-anchor",
+    "text-decoration",
+    "text-rendering",
+    "unicode-bidi",
+    "visibility",
+    "word-spacing",
+    "writing-mode",
+];
+
+struct Parser<'l> {
+    reader: Reader<'l>,
+}
+
+impl Style {
+    /// Create a list of declarations.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse a list of declarations.
+    #[inline]
+    pub fn parse(content: &str) -> Result<Self> {
+        Parser::new(content).process()
+    }
+
+    /// Return the number of declarations.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.declarations.len()
+    }
+
+    /// Check if there are no declarations.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+
+    /// Return the value of a property.
+    ///
+    /// Property names are matched case-insensitively.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.declarations
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set the value of a property returning the previous one.
+    ///
+    /// A new property is appended, and an existing one, which is matched
+    /// case-insensitively, keeps its name and position.
+    pub fn insert<T, U>(&mut self, name: T, value: U) -> Option<String>
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        let name = name.into();
+        let value = value.into();
+        match self
+            .declarations
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&name))
+        {
+            Some((_, current)) => Some(std::mem::replace(current, value)),
+            _ => {
+                self.declarations.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Remove a property returning its value.
+    ///
+    /// Property names are matched case-insensitively.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self
+            .declarations
+            .iter()
+            .position(|(key, _)| key.eq_ignore_ascii_case(name))?;
+        Some(self.declarations.remove(index).1)
+    }
+
+    /// Return an iterator over the declarations.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.declarations
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// Check if a name is that of a presentation attribute.
+pub(crate) fn is_presentation_attribute(name: &str) -> bool {
+    PRESENTATION_ATTRIBUTES.binary_search(&name).is_ok()
+}
+
+/// Split a value into the value proper and the flag indicating that of an
+/// `!important` declaration.
+pub(crate) fn split_important(value: &str) -> (&str, bool) {
+    let trimmed = value.trim_end();
+    let count = "important".len();
+    if trimmed.len() < count || !trimmed.is_char_boundary(trimmed.len() - count) {
+        return (value, false);
+    }
+    let (rest, suffix) = trimmed.split_at(trimmed.len() - count);
+    match rest.trim_end().strip_suffix('!') {
+        Some(rest) if suffix.eq_ignore_ascii_case("important") => (rest.trim_end(), true),
+        _ => (value, false),
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.declarations.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(";")?;
+            }
+            write!(formatter, "{}:{}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, U> FromIterator<(T, U)> for Style
+where
+    T: Into<String>,
+    U: Into<String>,
+{
+    fn from_iter<I>(iterator: I) -> Self
+    where
+        I: IntoIterator<Item = (T, U)>,
+    {
+        let mut style = Style::new();
+        for (name, value) in iterator {
+            style.insert(name, value);
+        }
+        style
+    }
+}
+
+impl From<Style> for Value {
+    #[inline]
+    fn from(style: Style) -> Self {
+        style.to_string().into()
+    }
+}
+
+macro_rules! raise(
+    ($parser:expr, $($argument:tt)*) => (
+        return Err(Error::new($parser.reader.position(), format!($($argument)*)))
+    );
+);
+
+impl<'l> Parser<'l> {
+    #[inline]
+    fn new(content: &'l str) -> Self {
+        Parser {
+            reader: Reader::new(content),
+        }
+    }
+
+    fn process(&mut self) -> Result<Style> {
+        let mut style = Style::new();
+        loop {
+            self.skip()?;
+            if self.reader.consume_char(';') {
+                continue;
+            }
+            if self.reader.is_done() {
+                break;
+            }
+            let name = match self.reader.capture(|reader| {
+                reader.consume_while(|c| !matches!(c, ':' | ';' | '/') && !c.is_whitespace())
+            }) {
+                Some(name) => name,
+                _ => raise!(self, "expected a property name"),
+            };
+            self.skip()?;
+            if !self.reader.consume_char(':') {
+                raise!(self, "expected ':' after '{}'", name);
+            }
+            let value = self.read_value()?;
+            if value.is_empty() {
+                raise!(self, "expected a value of '{}'", name);
+            }
+            let important = |value: &str| split_important(value).1;
+            match style.get(name) {
+                Some(current) if important(current) && !important(&value) => {}
+                _ => {
+                    style.insert(name, value);
+                }
+            }
+        }
+        Ok(style)
+    }
+
+    fn read_value(&mut self) -> Result<String> {
+        let mut value = String::new();
+        let mut depth = 0usize;
+        loop {
+            while self.skip_comment()? {}
+            match self.reader.peek() {
+                Some(';') if depth == 0 => break,
+                Some(quote @ ('"' | '\'')) => {
+                    value.push(quote);
+                    self.reader.next();
+                    loop {
+                        match self.reader.next() {
+                            Some('\\') => {
+                                value.push('\\');
+                                if let Some(c) = self.reader.next() {
+                                    value.push(c);
+                                }
+                            }
+                            Some(c) => {
+                                value.push(c);
+                                if c == quote {
+                                    break;
+                                }
+                            }
+                            _ => raise!(self, "found an unterminated string"),
+                        }
+                    }
+                }
+                Some(c) => {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth = depth.saturating_sub(1),
+                        _ => {}
+                    }
+                    value.push(c);
+                    self.reader.next();
+                }
+                _ => break,
+            }
+        }
+        let length = value.trim_end().len();
+        value.truncate(length);
+        Ok(value.trim_start().into())
+    }
+
+    fn skip(&mut self) -> Result<()> {
+        loop {
+            self.reader.consume_whitespace();
+            if !self.skip_comment()? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) -> Result<bool> {
+        let content = self.reader.peek_many().as_str();
+        if !content.starts_with("/*") {
+            return Ok(false);
+        }
+        match content[2..].find("*/") {
+            Some(end) => {
+                let end = self.reader.offset() + 2 + end + 2;
+                while self.reader.offset() < end {
+                    self.reader.next();
+                }
+                Ok(true)
+            }
+            _ => raise!(self, "found an unterminated comment"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_presentation_attribute, split_important, Style, PRESENTATION_ATTRIBUTES};
+
+    #[test]
+    fn style_parse() {
+        let style = Style::parse(
+            " fill : red ;; stroke-width:2/* comment */; font-family: 'a;b', \"c\\\"\"; \
+             fill: url(data:a;b) ",
+        )
+        .unwrap();
+        assert_eq!(
+            style.iter().collect::<Vec<_>>(),
+            &[
+                ("fill", "url(data:a;b)"),
+                ("stroke-width", "2"),
+                ("font-family", "'a;b', \"c\\\"\""),
+            ],
+        );
+        assert_eq!(
+            style.to_string(),
+            "fill:url(data:a;b);stroke-width:2;font-family:'a;b', \"c\\\"\"",
+        );
+        assert!(Style::parse("").unwrap().is_empty());
+
+        assert!(Style::parse("fill").is_err());
+        assert!(Style::parse("fill:").is_err());
+        assert!(Style::parse(":red").is_err());
+        assert!(Style::parse("font-family: 'a").is_err());
+        assert!(Style::parse("fill: red /*").is_err());
+    }
+
+    #[test]
+    fn style_insert() {
+        let mut style = Style::parse("fill:red;stroke:blue").unwrap();
+        assert_eq!(style.insert("fill", "green"), Some("red".into()));
+        assert_eq!(style.insert("opacity", "0.5"), None);
+        assert_eq!(style.remove("stroke"), Some("blue".into()));
+        assert_eq!(style.remove("stroke"), None);
+        assert_eq!(style.to_string(), "fill:green;opacity:0.5");
+    }
+
+    #[test]
+    fn style_presentation_attributes() {
+        assert!(PRESENTATION_ATTRIBUTES
+            .windows(2)
+            .all(|pair| pair[0] < pair[1]));
+        assert!(is_presentation_attribute("stroke-width"));
+        assert!(!is_presentation_attribute("width"));
+    }
+
+    #[test]
+    fn style_important() {
+        assert_eq!(split_important("red !important"), ("red", true));
+        assert_eq!(split_important("red ! IMPORTANT "), ("red", true));
+        assert_eq!(split_important("red"), ("red", false));
+        assert_eq!(split_important("important"), ("important", false));
+        assert_eq!(split_important("ménimportant"), ("ménimportant", false));
+
+        let style = Style::parse("fill: red !important; stroke: blue; FILL: green").unwrap();
+        assert_eq!(style.to_string(), "fill:red !important;stroke:blue");
+        let style = Style::parse("fill: red; Fill: green !important").unwrap();
+        assert_eq!(style.to_string(), "fill:green !important");
+    }
+
+    #[test]
+    fn style_case_insensitive() {
+        let mut style = Style::parse("Fill: red").unwrap();
+        assert_eq!(style.get("FILL"), Some("red"));
+        assert_eq!(style.insert("fill", "blue"), Some("red".into()));
+        assert_eq!(style.to_string(), "Fill:blue");
+        assert_eq!(style.remove("fIlL"), Some("blue".into()));
+        assert!(style.is_empty());
+    }
+}
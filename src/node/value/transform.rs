@@ -0,0 +1,441 @@
+//! The transforms.
+
+use std::fmt;
+use std::ops::{Deref, Mul};
+
+use super::Number;
+use crate::node::Value;
+use crate::parser::{Error, Reader, Result};
+
+/// A [transform][1] list.
+///
+/// [1]: https://www.w3.org/TR/SVG11/coords.html#TransformAttribute
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transform(Vec<TransformFunction>);
+
+/// A function of a transform list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransformFunction {
+    /// A matrix.
+    Matrix(Matrix),
+    /// A translation by `(tx, ty)`.
+    Translate(Number, Number),
+    /// A scaling by `(sx, sy)`.
+    Scale(Number, Number),
+    /// A rotation by an angle in degrees about a point `(cx, cy)`.
+    Rotate(Number, Number, Number),
+    /// A skew along the x axis by an angle in degrees.
+    SkewX(Number),
+    /// A skew along the y axis by an angle in degrees.
+    SkewY(Number),
+}
+
+/// An affine transformation matrix.
+///
+/// A point `(x, y)` is mapped to `(a x + c y + e, b x + d y + f)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix {
+    /// The first component.
+    pub a: Number,
+    /// The second component.
+    pub b: Number,
+    /// The third component.
+    pub c: Number,
+    /// The fourth component.
+    pub d: Number,
+    /// The fifth component.
+    pub e: Number,
+    /// The sixth component.
+    pub f: Number,
+}
+
+struct Parser<'l> {
+    reader: Reader<'l>,
+}
+
+impl Transform {
+    /// Create a transform list.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse a transform list.
+    #[inline]
+    pub fn parse(content: &str) -> Result<Self> {
+        Parser::new(content).process()
+    }
+
+    /// Append a function.
+    #[inline]
+    pub fn append(&mut self, function: TransformFunction) {
+        self.0.push(function);
+    }
+
+    /// Compose the functions into a single matrix.
+    pub fn to_matrix(&self) -> Matrix {
+        self.0.iter().fold(Matrix::identity(), |matrix, function| {
+            matrix * function.to_matrix()
+        })
+    }
+}
+
+impl TransformFunction {
+    /// Convert into a matrix.
+    pub fn to_matrix(&self) -> Matrix {
+        match *self {
+            TransformFunction::Matrix(matrix) => matrix,
+            TransformFunction::Translate(x, y) => Matrix::translate(x, y),
+            TransformFunction::Scale(x, y) => Matrix::scale(x, y),
+            TransformFunction::Rotate(angle, x, y) => {
+                Matrix::translate(x, y) * Matrix::rotate(angle) * Matrix::translate(-x, -y)
+            }
+            TransformFunction::SkewX(angle) => Matrix::skew_x(angle),
+            TransformFunction::SkewY(angle) => Matrix::skew_y(angle),
+        }
+    }
+}
+
+impl Matrix {
+    /// Create a matrix.
+    #[inline]
+    pub fn new(a: Number, b: Number, c: Number, d: Number, e: Number, f: Number) -> Self {
+        Matrix { a, b, c, d, e, f }
+    }
+
+    /// Create an identity matrix.
+    #[inline]
+    pub fn identity() -> Self {
+        Matrix::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Create a translation matrix.
+    #[inline]
+    pub fn translate(x: Number, y: Number) -> Self {
+        Matrix::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Create a scaling matrix.
+    #[inline]
+    pub fn scale(x: Number, y: Number) -> Self {
+        Matrix::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Create a rotation matrix given an angle in degrees.
+    #[inline]
+    pub fn rotate(angle: Number) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Matrix::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Create a skew matrix along the x axis given an angle in degrees.
+    #[inline]
+    pub fn skew_x(angle: Number) -> Self {
+        Matrix::new(1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    /// Create a skew matrix along the y axis given an angle in degrees.
+    #[inline]
+    pub fn skew_y(angle: Number) -> Self {
+        Matrix::new(1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Apply to a point.
+    #[inline]
+    pub fn apply(&self, (x, y): (Number, Number)) -> (Number, Number) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// Compute the determinant.
+    #[inline]
+    pub fn determinant(&self) -> Number {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Compute the inverse.
+    ///
+    /// `None` is returned if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+        Some(Matrix::new(
+            self.d / determinant,
+            -self.b / determinant,
+            -self.c / determinant,
+            self.a / determinant,
+            (self.c * self.f - self.d * self.e) / determinant,
+            (self.b * self.e - self.a * self.f) / determinant,
+        ))
+    }
+
+    /// Multiply by another matrix, which is applied first.
+    #[inline]
+    pub fn multiply(&self, other: &Self) -> Self {
+        Matrix::new(
+            self.a * other.a + self.c * other.b,
+            self.b * other.a + self.d * other.b,
+            self.a * other.c + self.c * other.d,
+            self.b * other.c + self.d * other.d,
+            self.a * other.e + self.c * other.f + self.e,
+            self.b * other.e + self.d * other.f + self.f,
+        )
+    }
+}
+
+impl Default for Matrix {
+    #[inline]
+    fn default() -> Self {
+        Matrix::identity()
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Matrix;
+
+    #[inline]
+    fn mul(self, other: Matrix) -> Self::Output {
+        self.multiply(&other)
+    }
+}
+
+impl Deref for Transform {
+    type Target = [TransformFunction];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<TransformFunction>> for Transform {
+    #[inline]
+    fn from(functions: Vec<TransformFunction>) -> Self {
+        Transform(functions)
+    }
+}
+
+impl From<Transform> for Vec<TransformFunction> {
+    #[inline]
+    fn from(Transform(functions): Transform) -> Self {
+        functions
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, function) in self.0.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(" ")?;
+            }
+            function.fmt(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TransformFunction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransformFunction::Matrix(matrix) => matrix.fmt(formatter),
+            TransformFunction::Translate(x, y) => write!(formatter, "translate({} {})", x, y),
+            TransformFunction::Scale(x, y) => write!(formatter, "scale({} {})", x, y),
+            TransformFunction::Rotate(angle, x, y) if x == 0.0 && y == 0.0 => {
+                write!(formatter, "rotate({})", angle)
+            }
+            TransformFunction::Rotate(angle, x, y) => {
+                write!(formatter, "rotate({} {} {})", angle, x, y)
+            }
+            TransformFunction::SkewX(angle) => write!(formatter, "skewX({})", angle),
+            TransformFunction::SkewY(angle) => write!(formatter, "skewY({})", angle),
+        }
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "matrix({} {} {} {} {} {})",
+            self.a, self.b, self.c, self.d, self.e, self.f,
+        )
+    }
+}
+
+impl From<Transform> for Value {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        transform.to_string().into()
+    }
+}
+
+impl From<TransformFunction> for Value {
+    #[inline]
+    fn from(function: TransformFunction) -> Self {
+        function.to_string().into()
+    }
+}
+
+impl From<Matrix> for Value {
+    #[inline]
+    fn from(matrix: Matrix) -> Self {
+        matrix.to_string().into()
+    }
+}
+
+macro_rules! raise(
+    ($parser:expr, $($argument:tt)*) => (
+        return Err(Error::new($parser.reader.position(), format!($($argument)*)))
+    );
+);
+
+impl<'l> Parser<'l> {
+    #[inline]
+    fn new(content: &'l str) -> Self {
+        Parser {
+            reader: Reader::new(content),
+        }
+    }
+
+    fn process(&mut self) -> Result<Transform> {
+        let mut functions = Vec::new();
+        self.reader.consume_whitespace();
+        while !self.reader.is_done() {
+            match self.read_function()? {
+                Some(function) => functions.push(function),
+                _ => raise!(self, "expected a transform function"),
+            }
+            self.reader.consume_whitespace();
+            let comma = self.reader.consume_char(',');
+            self.reader.consume_whitespace();
+            if comma && self.reader.is_done() {
+                raise!(self, "found a trailing comma in a transform list");
+            }
+        }
+        Ok(Transform(functions))
+    }
+
+    fn read_function(&mut self) -> Result<Option<TransformFunction>> {
+        use TransformFunction::*;
+
+        let name = match self
+            .reader
+            .capture(|reader| reader.consume_while(|c| c.is_ascii_alphabetic()))
+        {
+            Some(name) => name,
+            _ => return Ok(None),
+        };
+        self.reader.consume_whitespace();
+        if !self.reader.consume_char('(') {
+            raise!(self, "expected '(' after '{}'", name);
+        }
+        let arguments = self.read_arguments()?;
+        Ok(Some(match (name, &arguments[..]) {
+            ("matrix", &[a, b, c, d, e, f]) => Matrix(self::Matrix::new(a, b, c, d, e, f)),
+            ("translate", &[x]) => Translate(x, 0.0),
+            ("translate", &[x, y]) => Translate(x, y),
+            ("scale", &[x]) => Scale(x, x),
+            ("scale", &[x, y]) => Scale(x, y),
+            ("rotate", &[angle]) => Rotate(angle, 0.0, 0.0),
+            ("rotate", &[angle, x, y]) => Rotate(angle, x, y),
+            ("skewX", &[angle]) => SkewX(angle),
+            ("skewY", &[angle]) => SkewY(angle),
+            ("matrix" | "translate" | "scale" | "rotate" | "skewX" | "skewY", _) => {
+                raise!(self, "found a wrong number of arguments of '{}'", name)
+            }
+            _ => raise!(self, "found an unknown transform function '{}'", name),
+        }))
+    }
+
+    fn read_arguments(&mut self) -> Result<Vec<Number>> {
+        let mut arguments = Vec::new();
+        self.reader.consume_whitespace();
+        if self.reader.consume_char(')') {
+            return Ok(arguments);
+        }
+        loop {
+            let number = match self.reader.capture(|reader| reader.consume_number()) {
+                Some(number) => number,
+                _ => raise!(self, "expected a number"),
+            };
+            match number.parse() {
+                Ok(number) => arguments.push(number),
+                _ => raise!(self, "failed to parse a number '{}'", number),
+            }
+            self.reader.consume_whitespace();
+            let comma = self.reader.consume_char(',');
+            self.reader.consume_whitespace();
+            if self.reader.consume_char(')') {
+                if comma {
+                    raise!(self, "found a trailing comma in arguments");
+                }
+                return Ok(arguments);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Matrix, Transform, TransformFunction};
+
+    #[test]
+    fn matrix_algebra() {
+        let matrix = Matrix::translate(10.0, 20.0) * Matrix::scale(2.0, 3.0);
+        assert_close!(matrix.apply((1.0, 1.0)), (12.0, 23.0));
+
+        let inverse = matrix.inverse().unwrap();
+        assert_close!(inverse.apply((12.0, 23.0)), (1.0, 1.0));
+        assert_close!((inverse * matrix).apply((5.0, 7.0)), (5.0, 7.0));
+
+        assert_close!(Matrix::rotate(90.0).apply((1.0, 0.0)), (0.0, 1.0));
+        assert_close!(Matrix::skew_x(45.0).apply((0.0, 1.0)), (1.0, 1.0));
+        assert!(Matrix::scale(0.0, 1.0).inverse().is_none());
+    }
+
+    #[test]
+    fn transform_parse() {
+        let transform =
+            Transform::parse(" translate(10,20) rotate(45 5 5),scale(2)skewX(-1.5e1) ").unwrap();
+        assert_eq!(
+            &transform[..],
+            &[
+                TransformFunction::Translate(10.0, 20.0),
+                TransformFunction::Rotate(45.0, 5.0, 5.0),
+                TransformFunction::Scale(2.0, 2.0),
+                TransformFunction::SkewX(-15.0),
+            ],
+        );
+        assert_eq!(
+            transform.to_string(),
+            "translate(10 20) rotate(45 5 5) scale(2 2) skewX(-15)",
+        );
+
+        let transform = Transform::parse("translate(10) rotate(90 1 1)").unwrap();
+        assert_close!(transform.to_matrix().apply((2.0, 1.0)), (11.0, 2.0));
+        assert_eq!(
+            Transform::parse("matrix(1,0,0,1,2,3)")
+                .unwrap()
+                .to_matrix()
+                .to_string(),
+            "matrix(1 0 0 1 2 3)",
+        );
+        assert!(Transform::parse("").unwrap().is_empty());
+
+        assert!(Transform::parse("rotate(1 2)").is_err());
+        assert!(Transform::parse("turn(1)").is_err());
+        assert!(Transform::parse("scale(1").is_err());
+        assert!(Transform::parse("scale(1) foo").is_err());
+        assert!(Transform::parse("translate(1),").is_err());
+        assert!(Transform::parse(",scale(2)").is_err());
+        assert!(Transform::parse("scale(1,)").is_err());
+        assert!(Transform::parse("scale(,1)").is_err());
+        assert!(Transform::parse("scale(1),,scale(2)").is_err());
+        assert!(Transform::parse(" , ").is_err());
+        assert!(Transform::parse(" scale( 1 , 2 ) , translate( 3 ) ").is_ok());
+    }
+}
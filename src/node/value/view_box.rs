@@ -0,0 +1,358 @@
+//! The view boxes.
+
+use std::fmt;
+
+use super::{Matrix, Number};
+use crate::node::Value;
+use crate::parser::{Error, Reader, Result};
+
+/// A [view box][1].
+///
+/// [1]: https://www.w3.org/TR/SVG11/coords.html#ViewBoxAttribute
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewBox {
+    /// The minimal x coordinate.
+    pub x: Number,
+    /// The minimal y coordinate.
+    pub y: Number,
+    /// The width.
+    pub width: Number,
+    /// The height.
+    pub height: Number,
+}
+
+/// A [preserve-aspect-ratio][1] specification.
+///
+/// [1]: https://www.w3.org/TR/SVG11/coords.html#PreserveAspectRatioAttribute
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PreserveAspectRatio {
+    /// The flag ignoring the specification when it is given on an `image`
+    /// referencing an image with its own specification.
+    pub defer: bool,
+    /// The alignment.
+    pub align: Align,
+    /// The scaling.
+    pub meet_or_slice: MeetOrSlice,
+}
+
+macro_rules! implement {
+    ($($(#[$doc:meta])* $variant:ident => ($name:expr, $x:expr, $y:expr),)*) => (
+        /// An alignment of a view box within a viewport.
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum Align {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl Align {
+            /// Return the name.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Align::$variant => $name,)*
+                }
+            }
+
+            fn parse(content: &str) -> Option<Self> {
+                $(if content == $name {
+                    return Some(Align::$variant);
+                })*
+                None
+            }
+
+            fn factors(&self) -> Option<(Number, Number)> {
+                match self {
+                    $(Align::$variant => $x.map(|x| (x, $y)),)*
+                }
+            }
+        }
+    );
+}
+
+implement! {
+    /// No uniform scaling.
+    None => ("none", None, 0.0),
+    /// The minimal x and the minimal y.
+    XMinYMin => ("xMinYMin", Some(0.0), 0.0),
+    /// The middle x and the minimal y.
+    XMidYMin => ("xMidYMin", Some(0.5), 0.0),
+    /// The maximal x and the minimal y.
+    XMaxYMin => ("xMaxYMin", Some(1.0), 0.0),
+    /// The minimal x and the middle y.
+    XMinYMid => ("xMinYMid", Some(0.0), 0.5),
+    /// The middle x and the middle y.
+    XMidYMid => ("xMidYMid", Some(0.5), 0.5),
+    /// The maximal x and the middle y.
+    XMaxYMid => ("xMaxYMid", Some(1.0), 0.5),
+    /// The minimal x and the maximal y.
+    XMinYMax => ("xMinYMax", Some(0.0), 1.0),
+    /// The middle x and the maximal y.
+    XMidYMax => ("xMidYMax", Some(0.5), 1.0),
+    /// The maximal x and the maximal y.
+    XMaxYMax => ("xMaxYMax", Some(1.0), 1.0),
+}
+
+/// A scaling of a view box within a viewport.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MeetOrSlice {
+    /// Scale down until the view box is entirely visible.
+    #[default]
+    Meet,
+    /// Scale up until the viewport is entirely covered.
+    Slice,
+}
+
+impl ViewBox {
+    /// Create a view box.
+    #[inline]
+    pub fn new(x: Number, y: Number, width: Number, height: Number) -> Self {
+        ViewBox {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Parse a view box.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::new(content);
+        let mut numbers = [0.0; 4];
+        for (i, number) in numbers.iter_mut().enumerate() {
+            reader.consume_whitespace();
+            if i > 0 && reader.consume_char(',') {
+                reader.consume_whitespace();
+            }
+            let value = match reader.capture(|reader| reader.consume_number()) {
+                Some(value) => value,
+                _ => return Err(Error::new(reader.position(), "expected a number")),
+            };
+            *number = match value.parse() {
+                Ok(value) => value,
+                _ => {
+                    let message = format!("failed to parse a number '{}'", value);
+                    return Err(Error::new(reader.position(), message));
+                }
+            };
+        }
+        reader.consume_whitespace();
+        if !reader.is_done() {
+            return Err(Error::new(
+                reader.position(),
+                "found an unexpected ending of a view box",
+            ));
+        }
+        let [x, y, width, height] = numbers;
+        if width < 0.0 || height < 0.0 {
+            return Err(Error::new(
+                reader.position(),
+                "found a negative size of a view box",
+            ));
+        }
+        Ok(ViewBox::new(x, y, width, height))
+    }
+
+    /// Compute the transform mapping the user space onto a viewport of a
+    /// given size, which is to be inverted for the opposite direction.
+    ///
+    /// `None` is returned if the view box is empty, which disables rendering.
+    pub fn to_matrix(
+        &self,
+        width: Number,
+        height: Number,
+        aspect: &PreserveAspectRatio,
+    ) -> Option<Matrix> {
+        if self.width <= 0.0 || self.height <= 0.0 {
+            return None;
+        }
+        let (mut sx, mut sy) = (width / self.width, height / self.height);
+        let (fx, fy) = match aspect.align.factors() {
+            Some(factors) => {
+                let scale = match aspect.meet_or_slice {
+                    MeetOrSlice::Meet => sx.min(sy),
+                    MeetOrSlice::Slice => sx.max(sy),
+                };
+                sx = scale;
+                sy = scale;
+                factors
+            }
+            _ => (0.0, 0.0),
+        };
+        let tx = -self.x * sx + (width - self.width * sx) * fx;
+        let ty = -self.y * sy + (height - self.height * sy) * fy;
+        Some(Matrix::new(sx, 0.0, 0.0, sy, tx, ty))
+    }
+}
+
+impl PreserveAspectRatio {
+    /// Parse a preserve-aspect-ratio specification.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::new(content);
+        let mut result = PreserveAspectRatio::default();
+        let mut name = token(&mut reader);
+        if name == Some("defer") {
+            result.defer = true;
+            name = token(&mut reader);
+        }
+        result.align = match name.and_then(Align::parse) {
+            Some(align) => align,
+            _ => return Err(Error::new(reader.position(), "expected an alignment")),
+        };
+        result.meet_or_slice = match token(&mut reader) {
+            Some("meet") | None => MeetOrSlice::Meet,
+            Some("slice") => MeetOrSlice::Slice,
+            Some(name) => {
+                let message = format!("found an unknown scaling '{}'", name);
+                return Err(Error::new(reader.position(), message));
+            }
+        };
+        if token(&mut reader).is_some() {
+            return Err(Error::new(
+                reader.position(),
+                "found an unexpected ending of a preserve-aspect-ratio specification",
+            ));
+        }
+        Ok(result)
+    }
+}
+
+impl Default for Align {
+    #[inline]
+    fn default() -> Self {
+        Align::XMidYMid
+    }
+}
+
+impl fmt::Display for ViewBox {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{} {} {} {}",
+            self.x, self.y, self.width, self.height,
+        )
+    }
+}
+
+impl fmt::Display for PreserveAspectRatio {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.defer {
+            formatter.write_str("defer ")?;
+        }
+        formatter.write_str(self.align.name())?;
+        if self.meet_or_slice == MeetOrSlice::Slice {
+            formatter.write_str(" slice")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<(Number, Number, Number, Number)> for ViewBox {
+    #[inline]
+    fn from((x, y, width, height): (Number, Number, Number, Number)) -> Self {
+        ViewBox::new(x, y, width, height)
+    }
+}
+
+impl From<ViewBox> for Value {
+    #[inline]
+    fn from(view_box: ViewBox) -> Self {
+        view_box.to_string().into()
+    }
+}
+
+impl From<PreserveAspectRatio> for Value {
+    #[inline]
+    fn from(aspect: PreserveAspectRatio) -> Self {
+        aspect.to_string().into()
+    }
+}
+
+fn token<'l>(reader: &mut Reader<'l>) -> Option<&'l str> {
+    reader.consume_whitespace();
+    reader.capture(|reader| reader.consume_while(|c| !c.is_whitespace()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Align, MeetOrSlice, PreserveAspectRatio, ViewBox};
+
+    #[test]
+    fn preserve_aspect_ratio_parse() {
+        macro_rules! test(
+            ($content:expr, $defer:expr, $align:ident, $meet_or_slice:ident, $display:expr) => ({
+                let aspect = PreserveAspectRatio::parse($content).unwrap();
+                assert_eq!(
+                    aspect,
+                    PreserveAspectRatio {
+                        defer: $defer,
+                        align: Align::$align,
+                        meet_or_slice: MeetOrSlice::$meet_or_slice,
+                    },
+                );
+                assert_eq!(aspect.to_string(), $display);
+            });
+        );
+
+        test!("xMidYMid", false, XMidYMid, Meet, "xMidYMid");
+        test!(" none ", false, None, Meet, "none");
+        test!("xMinYMax slice", false, XMinYMax, Slice, "xMinYMax slice");
+        test!(
+            "defer xMaxYMin meet",
+            true,
+            XMaxYMin,
+            Meet,
+            "defer xMaxYMin"
+        );
+
+        assert!(PreserveAspectRatio::parse("").is_err());
+        assert!(PreserveAspectRatio::parse("xmidymid").is_err());
+        assert!(PreserveAspectRatio::parse("xMidYMid cover").is_err());
+        assert!(PreserveAspectRatio::parse("xMidYMid meet slice").is_err());
+    }
+
+    #[test]
+    fn view_box_parse() {
+        assert_eq!(
+            ViewBox::parse(" 0,-10 70.5 7e1 ").unwrap(),
+            ViewBox::new(0.0, -10.0, 70.5, 70.0),
+        );
+        assert_eq!(ViewBox::new(0.0, 0.0, 70.0, 70.0).to_string(), "0 0 70 70");
+
+        assert!(ViewBox::parse("0 0 70").is_err());
+        assert!(ViewBox::parse("0 0 70 70 70").is_err());
+        assert!(ViewBox::parse("0 0 -70 70").is_err());
+    }
+
+    #[test]
+    fn view_box_to_matrix() {
+        let view_box = ViewBox::new(10.0, 10.0, 100.0, 50.0);
+
+        macro_rules! test(
+            ($aspect:expr, $point:expr, $expected:expr) => (
+                assert_eq!(
+                    view_box
+                        .to_matrix(400.0, 400.0, &PreserveAspectRatio::parse($aspect).unwrap())
+                        .unwrap()
+                        .apply($point),
+                    $expected,
+                );
+            );
+        );
+
+        test!("xMidYMid", (10.0, 10.0), (0.0, 100.0));
+        test!("xMidYMid", (110.0, 60.0), (400.0, 300.0));
+        test!("xMinYMin", (10.0, 10.0), (0.0, 0.0));
+        test!("xMaxYMax slice", (110.0, 60.0), (400.0, 400.0));
+        test!("xMinYMin slice", (10.0, 10.0), (0.0, 0.0));
+        test!("none", (110.0, 60.0), (400.0, 400.0));
+
+        let matrix = view_box
+            .to_matrix(400.0, 400.0, &Default::default())
+            .unwrap()
+            .inverse()
+            .unwrap();
+        assert_eq!(matrix.apply((200.0, 200.0)), (60.0, 35.0));
+
+        let view_box = ViewBox::new(0.0, 0.0, 0.0, 50.0);
+        assert_eq!(view_box.to_matrix(400.0, 400.0, &Default::default()), None);
+    }
+}
@@ -0,0 +1,193 @@
+//! The entities.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use super::Reader;
+
+/// The maximal number of bytes produced by expanding declared entities in a
+/// document, which guards against exponential expansion.
+const MAXIMAL_EXPANSION: usize = 1 << 20;
+
+/// Entities declared in a document type definition.
+#[derive(Debug, Default)]
+pub struct Entities {
+    values: HashMap<String, String>,
+    expansion: usize,
+}
+
+impl Entities {
+    /// Create entities.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn expand(&mut self, name: &str) -> Option<Option<&str>> {
+        let value = match self.values.get(name) {
+            Some(value) => value,
+            _ => return Some(None),
+        };
+        self.expansion += value.len();
+        if self.expansion > MAXIMAL_EXPANSION {
+            return None;
+        }
+        Some(Some(value))
+    }
+}
+
+/// Decode character and entity references.
+///
+/// `None` is returned if the expansion of declared entities exceeds the limit.
+pub fn decode<'l>(content: &'l str, entities: &mut Entities) -> Option<Cow<'l, str>> {
+    if !content.contains('&') {
+        return Some(Cow::Borrowed(content));
+    }
+    let mut result = String::with_capacity(content.len());
+    let mut content = content;
+    while let Some(start) = content.find('&') {
+        result.push_str(&content[..start]);
+        content = &content[start..];
+        let value = match content.find(';') {
+            Some(end) => resolve(&content[1..end], entities)?.map(|value| (end, value)),
+            _ => None,
+        };
+        match value {
+            Some((end, value)) => {
+                result.push_str(&value);
+                content = &content[(end + 1)..];
+            }
+            _ => {
+                result.push('&');
+                content = &content[1..];
+            }
+        }
+    }
+    result.push_str(content);
+    Some(Cow::Owned(result))
+}
+
+// https://www.w3.org/TR/REC-xml/#sec-internal-ent
+//
+// `None` is returned if the expansion of declared entities exceeds the limit.
+pub fn declare(content: &str, entities: &mut Entities) -> Option<()> {
+    let subset = match (content.find('['), content.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &content[(start + 1)..end],
+        _ => return Some(()),
+    };
+    for (start, _) in subset.match_indices("<!ENTITY") {
+        let mut reader = Reader::new(&subset[(start + 8)..]);
+        if !reader.consume_whitespace() || reader.consume_char('%') {
+            continue;
+        }
+        let name = match reader.capture(|reader| reader.consume_name()) {
+            Some(name) => name,
+            _ => continue,
+        };
+        reader.consume_whitespace();
+        let value = match reader.capture(|reader| reader.consume_literal()) {
+            Some(value) => &value[1..(value.len() - 1)],
+            _ => continue,
+        };
+        if !entities.values.contains_key(name) {
+            let value = decode(value, entities)?.into_owned();
+            entities.values.insert(name.to_string(), value);
+        }
+    }
+    Some(())
+}
+
+fn resolve<'l>(name: &str, entities: &'l mut Entities) -> Option<Option<Cow<'l, str>>> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix('x') {
+            Some(number) => u32::from_str_radix(number, 16).ok(),
+            _ => number.parse().ok(),
+        };
+        return Some(
+            code.and_then(char::from_u32)
+                .map(|value| Cow::Owned(value.to_string())),
+        );
+    }
+    Some(Some(Cow::Borrowed(match name {
+        "amp" => "&",
+        "apos" => "'",
+        "gt" => ">",
+        "lt" => "<",
+        "quot" => "\"",
+        _ => match entities.expand(name)? {
+            Some(value) => value,
+            _ => return Some(None),
+        },
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{declare, decode, Entities};
+
+    #[test]
+    fn decode_borrowed() {
+        let mut entities = Entities::new();
+        assert!(matches!(
+            decode("foo", &mut entities),
+            Some(Cow::Borrowed("foo"))
+        ));
+    }
+
+    #[test]
+    fn decode_owned() {
+        macro_rules! test(
+            ($content:expr, $value:expr) => (
+                assert_eq!(decode($content, &mut Entities::new()).unwrap(), $value)
+            );
+        );
+
+        test!("&lt;foo&gt;", "<foo>");
+        test!("&quot;foo&apos;", "\"foo'");
+        test!("foo &amp;amp; bar", "foo &amp; bar");
+        test!("&#65;&#x42;&#x63;", "ABc");
+        test!("&#10;", "\n");
+        test!("&foo; & bar", "&foo; & bar");
+        test!("&#xD800;", "&#xD800;");
+    }
+
+    #[test]
+    fn declare_internal_subset() {
+        let mut entities = Entities::new();
+        let result = declare(
+            r#"<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "svg11.dtd" [
+                <!ENTITY ns_svg "http://www.w3.org/2000/svg">
+                <!ENTITY % parameter "foo">
+                <!ENTITY external SYSTEM "bar.xml">
+                <!ENTITY nested '&#60;&ns_svg;'>
+                <!ENTITY ns_svg "baz">
+            ]>"#,
+            &mut entities,
+        );
+
+        assert!(result.is_some());
+        assert_eq!(entities.values.len(), 2);
+        assert_eq!(entities.values["ns_svg"], "http://www.w3.org/2000/svg");
+        assert_eq!(entities.values["nested"], "<http://www.w3.org/2000/svg");
+    }
+
+    #[test]
+    fn declare_exponential_expansion() {
+        let mut entities = Entities::new();
+        let mut content = String::from("<!DOCTYPE svg [<!ENTITY a0 \"aaaaaaaaaa\">");
+        for i in 1..10 {
+            let references = format!("&a{};", i - 1).repeat(10);
+            content.push_str(&format!("<!ENTITY a{} \"{}\">", i, references));
+        }
+        content.push_str("]>");
+        assert!(declare(&content, &mut entities).is_none());
+
+        let mut entities = Entities::new();
+        let content = r#"<!DOCTYPE svg [<!ENTITY a "aaaaaaaaaaaaaaaa">]>"#;
+        assert!(declare(content, &mut entities).is_some());
+        let content = "&a;".repeat((1 << 16) + 1);
+        assert!(decode(&content, &mut entities).is_none());
+    }
+}
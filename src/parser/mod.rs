@@ -1,12 +1,22 @@
 //! The parser.
 
+use std::borrow::Cow;
+use std::ops::Range;
+
 use crate::node::element::tag::{Tag, Type};
+use crate::node::namespace::Scope;
 use crate::node::Attributes;
 
+mod entity;
 mod error;
 mod reader;
+mod span;
+mod stream;
 
+pub(crate) use self::entity::{decode, Entities};
 pub use self::error::Error;
+pub use self::span::{Span, Spanned};
+pub use self::stream::Stream;
 
 #[doc(hidden)]
 pub use self::reader::Reader;
@@ -14,8 +24,24 @@ pub use self::reader::Reader;
 /// A parser.
 pub struct Parser<'l> {
     reader: Reader<'l>,
+    state: State,
+    start: usize,
+    range: Range<usize>,
+    sources: Vec<(&'l str, &'l str)>,
+}
+
+#[derive(Default)]
+struct State {
+    entities: Entities,
+    namespaces: Scope,
+    leaving: bool,
+    stack: Option<Vec<Open>>,
+    whitespace: Whitespace,
+    spaces: Vec<bool>,
 }
 
+type Open = (String, (usize, usize), Range<usize>);
+
 /// An event.
 #[derive(Debug)]
 pub enum Event<'l> {
@@ -23,40 +49,131 @@ pub enum Event<'l> {
     Error(Error),
     /// A tag.
     Tag(&'l str, Type, Attributes),
-    /// A text.
-    Text(&'l str),
+    /// A text with character and entity references decoded.
+    Text(Cow<'l, str>),
     /// A comment.
     Comment(&'l str),
+    /// A CDATA section without the delimiters.
+    CData(&'l str),
     /// A declaration.
     Declaration(&'l str),
     /// An instruction.
     Instruction(&'l str),
 }
 
+/// An owned event.
+#[derive(Debug)]
+pub enum OwnedEvent {
+    /// An error.
+    Error(Error),
+    /// A tag.
+    Tag(String, Type, Attributes),
+    /// A text with character and entity references decoded.
+    Text(String),
+    /// A comment.
+    Comment(String),
+    /// A CDATA section without the delimiters.
+    CData(String),
+    /// A declaration.
+    Declaration(String),
+    /// An instruction.
+    Instruction(String),
+}
+
+/// A treatment of whitespace in text.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Whitespace {
+    /// Trim text and skip text consisting only of whitespace.
+    #[default]
+    Trim,
+    /// Keep text as is.
+    Preserve,
+    /// Keep text as is within elements where [`xml:space`][1] is set to
+    /// `preserve` and trim it elsewhere.
+    ///
+    /// [1]: https://www.w3.org/TR/REC-xml/#sec-white-space
+    XmlSpace,
+}
+
 /// A result.
 pub type Result<T> = std::result::Result<T, Error>;
 
 macro_rules! raise(
-    ($parser:expr, $($argument:tt)*) => (
+    ($parser:expr, $($argument:tt)*) => ({
+        $parser.range = $parser.start..$parser.reader.offset();
         return Some(Event::Error(Error::new($parser.reader.position(), format!($($argument)*))))
-    );
+    });
 );
 
 impl<'l> Parser<'l> {
     /// Create a parser.
     #[inline]
     pub fn new(content: &'l str) -> Self {
+        Parser::resume(Reader::new(content), State::default())
+    }
+
+    fn resume(reader: Reader<'l>, state: State) -> Self {
         Parser {
-            reader: Reader::new(content),
+            reader,
+            state,
+            start: 0,
+            range: 0..0,
+            sources: Vec::new(),
         }
     }
 
+    /// Pair each event with its span.
+    #[inline]
+    pub fn spanned(self) -> Spanned<'l> {
+        Spanned::new(self)
+    }
+
+    /// Return the namespace declarations in scope of the most recent tag.
+    ///
+    /// The scope includes the declarations made by the tag itself.
+    #[inline]
+    pub fn namespaces(&self) -> &Scope {
+        &self.state.namespaces
+    }
+
+    /// Check that elements are properly nested.
+    ///
+    /// When enabled, mismatched end tags, end tags without a start tag, and
+    /// elements left unclosed at the end of the content are reported via
+    /// `Event::Error`.
+    pub fn well_formed(mut self, value: bool) -> Self {
+        self.state.stack = if value { Some(Vec::new()) } else { None };
+        self
+    }
+
+    /// Set the treatment of whitespace in text.
+    pub fn whitespace(mut self, value: Whitespace) -> Self {
+        self.state.whitespace = value;
+        self
+    }
+
+    #[inline]
+    pub(crate) fn position(&self) -> (usize, usize) {
+        self.reader.position()
+    }
+
+    fn advance(&mut self) -> Option<Event<'l>> {
+        if self.state.leaving {
+            self.state.namespaces.pop();
+            self.state.leaving = false;
+        }
+        self.next_text().or_else(|| self.next_angle())
+    }
+
     fn next_angle(&mut self) -> Option<Event<'l>> {
-        let content: String = self.reader.peek_many().take(4).collect();
+        self.start = self.reader.offset();
+        let content: String = self.reader.peek_many().take(9).collect();
         if content.is_empty() {
             None
         } else if content.starts_with("<!--") {
             self.read_comment()
+        } else if content.starts_with("<![CDATA[") {
+            self.read_cdata()
         } else if content.starts_with("<!") {
             self.read_declaration()
         } else if content.starts_with("<?") {
@@ -69,40 +186,151 @@ impl<'l> Parser<'l> {
     }
 
     fn next_text(&mut self) -> Option<Event<'l>> {
-        self.reader
-            .capture(|reader| reader.consume_until_char('<'))
-            .map(Event::Text)
+        let exact = match self.state.whitespace {
+            Whitespace::Trim => false,
+            Whitespace::Preserve => true,
+            Whitespace::XmlSpace => self.state.spaces.last() == Some(&true),
+        };
+        let content = if exact {
+            self.reader
+                .capture_exact(|reader| reader.consume_until_char('<'))?
+        } else {
+            self.reader
+                .capture(|reader| reader.consume_until_char('<'))?
+        };
+        self.range = self.reader.locate(content);
+        match decode(content, &mut self.state.entities) {
+            Some(content) => Some(Event::Text(content)),
+            _ => raise!(self, "found entities expanding beyond the limit"),
+        }
+    }
+
+    fn read_cdata(&mut self) -> Option<Event<'l>> {
+        match self.reader.capture(|reader| reader.consume_cdata()) {
+            None => raise!(self, "found a malformed CDATA section"),
+            Some(content) => {
+                self.range = self.reader.locate(content);
+                Some(Event::CData(&content[9..(content.len() - 3)]))
+            }
+        }
     }
 
     fn read_comment(&mut self) -> Option<Event<'l>> {
         match self.reader.capture(|reader| reader.consume_comment()) {
             None => raise!(self, "found a malformed comment"),
-            Some(content) => Some(Event::Comment(content)),
+            Some(content) => {
+                self.range = self.reader.locate(content);
+                Some(Event::Comment(content))
+            }
         }
     }
 
     fn read_declaration(&mut self) -> Option<Event<'l>> {
         match self.reader.capture(|reader| reader.consume_declaration()) {
             None => raise!(self, "found a malformed declaration"),
-            Some(content) => Some(Event::Declaration(content)),
+            Some(content) => {
+                self.range = self.reader.locate(content);
+                if content.starts_with("<!DOCTYPE")
+                    && entity::declare(content, &mut self.state.entities).is_none()
+                {
+                    raise!(self, "found entities expanding beyond the limit");
+                }
+                Some(Event::Declaration(content))
+            }
         }
     }
 
     fn read_instruction(&mut self) -> Option<Event<'l>> {
         match self.reader.capture(|reader| reader.consume_instruction()) {
             None => raise!(self, "found a malformed instruction"),
-            Some(content) => Some(Event::Instruction(content)),
+            Some(content) => {
+                self.range = self.reader.locate(content);
+                Some(Event::Instruction(content))
+            }
         }
     }
 
     fn read_tag(&mut self) -> Option<Event<'l>> {
-        match self.reader.capture(|reader| reader.consume_tag()) {
-            None => raise!(self, "found a malformed tag"),
-            Some(content) => Some(match Tag::parse(&content[1..content.len() - 1]) {
-                Ok(Tag(name, kind, attributes)) => Event::Tag(name, kind, attributes),
-                Err(error) => Event::Error(error),
-            }),
+        let position = self.reader.position();
+        let content = match self.reader.capture(|reader| reader.consume_tag()) {
+            Some(content) => content,
+            _ => raise!(self, "found a malformed tag"),
+        };
+        self.range = self.reader.locate(content);
+        let (Tag(name, kind, attributes), sources) =
+            match Tag::parse_with(&content[1..content.len() - 1], &mut self.state.entities) {
+                Ok(result) => result,
+                Err(error) => return Some(Event::Error(error)),
+            };
+        if let Some(error) = self.check(name, kind, position) {
+            return Some(Event::Error(error));
+        }
+        if kind != Type::End {
+            self.state.namespaces.push(&attributes);
+        }
+        self.state.leaving = kind != Type::Start;
+        if self.state.whitespace == Whitespace::XmlSpace {
+            match kind {
+                Type::Start => {
+                    let inherited = self.state.spaces.last() == Some(&true);
+                    self.state.spaces.push(match attributes.get("xml:space") {
+                        Some(value) => *value == "preserve",
+                        _ => inherited,
+                    });
+                }
+                Type::End => {
+                    self.state.spaces.pop();
+                }
+                Type::Empty => {}
+            }
+        }
+        self.sources = sources;
+        Some(Event::Tag(name, kind, attributes))
+    }
+
+    fn check(&mut self, name: &'l str, kind: Type, position: (usize, usize)) -> Option<Error> {
+        let stack = self.state.stack.as_mut()?;
+        match kind {
+            Type::Start => stack.push((name.into(), position, self.range.clone())),
+            Type::End => match stack.iter().rposition(|(other, _, _)| other == name) {
+                Some(index) if index + 1 == stack.len() => {
+                    stack.pop();
+                }
+                Some(index) => {
+                    let error = Error::new(
+                        position,
+                        format!(
+                            "found an end tag '{}' while expecting '{}'",
+                            name,
+                            stack[stack.len() - 1].0,
+                        ),
+                    );
+                    for _ in index..stack.len() {
+                        self.state.namespaces.pop();
+                        self.state.spaces.pop();
+                    }
+                    stack.truncate(index);
+                    return Some(error);
+                }
+                _ => {
+                    return Some(Error::new(
+                        position,
+                        format!("found an end tag '{}' without a start tag", name),
+                    ))
+                }
+            },
+            Type::Empty => {}
         }
+        None
+    }
+
+    fn finish(&mut self) -> Option<Event<'l>> {
+        let (name, position, range) = self.state.stack.as_mut()?.pop()?;
+        self.range = range;
+        Some(Event::Error(Error::new(
+            position,
+            format!("found an unclosed element '{}'", name),
+        )))
     }
 }
 
@@ -110,13 +338,146 @@ impl<'l> Iterator for Parser<'l> {
     type Item = Event<'l>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next_text().or_else(|| self.next_angle())
+        self.advance().or_else(|| self.finish())
+    }
+}
+
+impl From<Event<'_>> for OwnedEvent {
+    fn from(event: Event<'_>) -> Self {
+        match event {
+            Event::Error(error) => OwnedEvent::Error(error),
+            Event::Tag(name, kind, attributes) => OwnedEvent::Tag(name.into(), kind, attributes),
+            Event::Text(content) => OwnedEvent::Text(content.into()),
+            Event::Comment(content) => OwnedEvent::Comment(content.into()),
+            Event::CData(content) => OwnedEvent::CData(content.into()),
+            Event::Declaration(content) => OwnedEvent::Declaration(content.into()),
+            Event::Instruction(content) => OwnedEvent::Instruction(content.into()),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::{Event, Parser};
+    use crate::parser::{Event, Parser, Stream, Type};
+
+    const _: () = {
+        const fn check<T: Send + Sync>() {}
+        check::<Parser<'static>>();
+        check::<Stream<&'static [u8]>>();
+    };
+
+    #[test]
+    fn next_cdata() {
+        let mut parser = Parser::new("<style><![CDATA[a > b && c < d]]></style>");
+
+        assert!(matches!(parser.next().unwrap(), Event::Tag("style", _, _)));
+        match parser.next().unwrap() {
+            Event::CData(value) => assert_eq!(value, "a > b && c < d"),
+            _ => unreachable!(),
+        }
+        assert!(matches!(parser.next().unwrap(), Event::Tag("style", _, _)));
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    fn next_well_formed() {
+        macro_rules! test(
+            ($content:expr, $($expected:expr),*) => ({
+                let errors = Parser::new($content)
+                    .well_formed(true)
+                    .filter_map(|event| match event {
+                        Event::Error(error) => Some(error.to_string()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let expected: &[&str] = &[$($expected),*];
+                assert_eq!(errors, expected);
+            });
+        );
+
+        test!("<svg><g/><g></g></svg>",);
+        test!(
+            "<svg>\n  <g></svg>",
+            "found an end tag 'svg' while expecting 'g' (line 2, column 6)"
+        );
+        test!(
+            "<svg></g></svg>",
+            "found an end tag 'g' without a start tag (line 1, column 6)"
+        );
+        test!(
+            "<svg>\n<g>",
+            "found an unclosed element 'g' (line 2, column 1)",
+            "found an unclosed element 'svg' (line 1, column 1)"
+        );
+
+        assert_eq!(Parser::new("<svg></g>").count(), 2);
+    }
+
+    #[test]
+    fn next_namespaces() {
+        use crate::node::namespace::{Name, SVG, XLINK};
+
+        let mut parser = Parser::new(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+                <use xlink:href="#foo"/>
+                <div xmlns="http://www.w3.org/1999/xhtml"></div>
+                <g/>
+            </svg>"##,
+        );
+
+        macro_rules! test(
+            ($name:expr, $namespace:expr) => ({
+                match parser.next().unwrap() {
+                    Event::Tag(name, _, _) => assert_eq!(
+                        parser.namespaces().resolve_element(name),
+                        Some(Name { namespace: Some($namespace), local: $name }),
+                    ),
+                    _ => unreachable!(),
+                }
+            });
+        );
+
+        test!("svg", SVG);
+        test!("use", SVG);
+        assert_eq!(
+            parser.namespaces().resolve_attribute("xlink:href"),
+            Some(Name {
+                namespace: Some(XLINK),
+                local: "href"
+            }),
+        );
+        test!("div", "http://www.w3.org/1999/xhtml");
+        test!("div", "http://www.w3.org/1999/xhtml");
+        test!("g", SVG);
+        test!("svg", SVG);
+    }
+
+    #[test]
+    fn next_namespaces_mismatched() {
+        let mut parser =
+            Parser::new(r#"<svg xmlns="foo"><g xmlns="bar"><a xmlns="baz"></g><g/></svg>"#)
+                .well_formed(true);
+
+        let mut namespaces = vec![];
+        while let Some(event) = parser.next() {
+            let kind = match event {
+                Event::Tag(_, kind, _) => Some(kind),
+                _ => None,
+            };
+            namespaces.push((kind, parser.namespaces().lookup(None).map(String::from)));
+        }
+        assert_eq!(
+            namespaces,
+            &[
+                (Some(Type::Start), Some("foo".into())),
+                (Some(Type::Start), Some("bar".into())),
+                (Some(Type::Start), Some("baz".into())),
+                (None, Some("foo".into())),
+                (Some(Type::Empty), Some("foo".into())),
+                (Some(Type::End), Some("foo".into())),
+            ],
+        );
+    }
 
     #[test]
     fn next_tag() {
@@ -133,6 +494,8 @@ mod tests {
         test!("<foo>", "foo");
         test!("<foo/>", "foo");
         test!("  <foo/>", "foo");
+        test!("<foo bar='a > b'>", "foo");
+        test!(r#"<foo bar="content:'>'"/>"#, "foo");
     }
 
     #[test]
@@ -150,5 +513,65 @@ mod tests {
         test!("foo <bar>", "foo");
         test!("  foo<bar>", "foo");
         test!("foo> <bar>", "foo>");
+        test!("foo &lt;&#x62;ar&gt; <baz>", "foo <bar>");
+    }
+
+    #[test]
+    fn next_text_whitespace() {
+        use crate::parser::Whitespace;
+
+        macro_rules! test(
+            ($whitespace:ident, $($value:expr),*) => ({
+                let content = "<a> b <c xml:space='preserve'> d <e xml:space='default'> f </e> g </c>\n</a>";
+                let texts = Parser::new(content)
+                    .whitespace(Whitespace::$whitespace)
+                    .filter_map(|event| match event {
+                        Event::Text(value) => Some(value.into_owned()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let expected: &[&str] = &[$($value),*];
+                assert_eq!(texts, expected);
+            });
+        );
+
+        test!(Trim, "b", "d", "f", "g");
+        test!(Preserve, " b ", " d ", " f ", " g ", "\n");
+        test!(XmlSpace, "b", " d ", "f", " g ");
+    }
+
+    #[test]
+    fn next_with_entities() {
+        let mut parser = Parser::new(
+            r#"<!DOCTYPE svg [<!ENTITY foo "bar">]><svg baz="&foo; &amp;">&foo;</svg>"#,
+        );
+
+        assert!(matches!(parser.next().unwrap(), Event::Declaration(_)));
+        match parser.next().unwrap() {
+            Event::Tag("svg", _, attributes) => assert_eq!(attributes["baz"], "bar &"),
+            _ => unreachable!(),
+        }
+        match parser.next().unwrap() {
+            Event::Text(value) => assert_eq!(value, "bar"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn next_with_entities_expanding() {
+        let mut content = String::from(r#"<!DOCTYPE svg [<!ENTITY a0 "aaaaaaaaaa">"#);
+        for i in 1..10 {
+            let references = format!("&a{};", i - 1).repeat(10);
+            content.push_str(&format!(r#"<!ENTITY a{} "{}">"#, i, references));
+        }
+        content.push_str("]><svg>&a9;</svg>");
+        let mut parser = Parser::new(&content);
+
+        match parser.next().unwrap() {
+            Event::Error(error) => {
+                assert!(error.to_string().contains("expanding beyond the limit"))
+            }
+            _ => unreachable!(),
+        }
     }
 }
@@ -1,4 +1,5 @@
 use std::iter::Peekable;
+use std::ops::Range;
 use std::str::Chars;
 
 pub struct Reader<'l> {
@@ -12,16 +13,31 @@ pub struct Reader<'l> {
 impl<'l> Reader<'l> {
     #[inline]
     pub fn new(content: &'l str) -> Self {
+        Reader::with_position(content, (1, 1))
+    }
+
+    #[inline]
+    pub fn with_position(content: &'l str, (line, column): (usize, usize)) -> Self {
         Reader {
-            line: 1,
-            column: 1,
+            line,
+            column,
             offset: 0,
             content,
             cursor: content.chars().peekable(),
         }
     }
 
+    #[inline]
     pub fn capture<F>(&mut self, block: F) -> Option<&'l str>
+    where
+        F: Fn(&mut Reader<'l>) -> bool,
+    {
+        self.capture_exact(block)
+            .map(str::trim)
+            .filter(|content| !content.is_empty())
+    }
+
+    pub fn capture_exact<F>(&mut self, block: F) -> Option<&'l str>
     where
         F: Fn(&mut Reader<'l>) -> bool,
     {
@@ -29,7 +45,7 @@ impl<'l> Reader<'l> {
         if !block(self) {
             return None;
         }
-        let content = &self.content[start..self.offset].trim();
+        let content = &self.content[start..self.offset];
         if content.is_empty() {
             None
         } else {
@@ -76,6 +92,19 @@ impl<'l> Reader<'l> {
         self.consume_char(if single { '\'' } else { '"' })
     }
 
+    // https://www.w3.org/TR/REC-xml/#sec-cdata-sect
+    pub fn consume_cdata(&mut self) -> bool {
+        if !"<![CDATA[".chars().all(|c| self.consume_char(c)) {
+            return false;
+        }
+        while !self.peek_many().as_str().starts_with("]]>") {
+            if self.next().is_none() {
+                return false;
+            }
+        }
+        "]]>".chars().all(|c| self.consume_char(c))
+    }
+
     pub fn consume_char(&mut self, target: char) -> bool {
         match self.peek() {
             Some(c) if c == target => {
@@ -131,11 +160,26 @@ impl<'l> Reader<'l> {
         consumed
     }
 
+    // https://www.w3.org/TR/REC-xml/#NT-doctypedecl
     pub fn consume_declaration(&mut self) -> bool {
-        self.consume_char('<')
-            && self.consume_char('!')
-            && self.consume_until_char('>')
-            && self.consume_char('>')
+        if !self.consume_char('<') || !self.consume_char('!') {
+            return false;
+        }
+        let mut subset = false;
+        loop {
+            self.consume_until_any(if subset { "'\"]" } else { "'\"[>" });
+            match self.peek() {
+                Some('\'') | Some('"') => {
+                    if !self.consume_literal() {
+                        return false;
+                    }
+                }
+                Some('[') => subset = self.consume_char('['),
+                Some(']') => subset = !self.consume_char(']'),
+                _ => break,
+            }
+        }
+        self.consume_char('>')
     }
 
     #[inline]
@@ -163,23 +207,35 @@ impl<'l> Reader<'l> {
         F: Fn(char) -> bool,
     {
         match self.peek() {
-            Some(c) => {
-                if check(c) {
-                    self.next();
-                    true
-                } else {
-                    false
-                }
+            Some(c) if check(c) => {
+                self.next();
+                true
             }
             _ => false,
         }
     }
 
     pub fn consume_instruction(&mut self) -> bool {
-        self.consume_char('<')
-            && self.consume_char('?')
-            && self.consume_until_char('>')
-            && self.consume_char('>')
+        if !self.consume_char('<') || !self.consume_char('?') {
+            return false;
+        }
+        while !self.peek_many().as_str().starts_with("?>") {
+            if self.next().is_none() {
+                return false;
+            }
+        }
+        self.consume_char('?') && self.consume_char('>')
+    }
+
+    // https://www.w3.org/TR/REC-xml/#NT-SystemLiteral
+    pub fn consume_literal(&mut self) -> bool {
+        let quote = match self.peek() {
+            Some(c) if c == '\'' || c == '"' => c,
+            _ => return false,
+        };
+        self.next();
+        self.consume_until_char(quote);
+        self.consume_char(quote)
     }
 
     // https://www.w3.org/TR/REC-xml/#NT-Name
@@ -208,9 +264,18 @@ impl<'l> Reader<'l> {
         } else if !self.consume_char('.') || !self.consume_digits() {
             return false;
         }
-        if !self.consume_char('e') && !self.consume_char('E') {
+        let mut chars = self.peek_many();
+        if !matches!(chars.next(), Some('e' | 'E')) {
+            return true;
+        }
+        let next = match chars.next() {
+            Some('+' | '-') => chars.next(),
+            next => next,
+        };
+        if !next.is_some_and(|c| c.is_ascii_digit()) {
             return true;
         }
+        self.next();
         self.consume_sign();
         self.consume_digits()
     }
@@ -234,8 +299,25 @@ impl<'l> Reader<'l> {
         self.consume_char('+') || self.consume_char('-')
     }
 
+    // https://www.w3.org/TR/REC-xml/#sec-starttags
     pub fn consume_tag(&mut self) -> bool {
-        self.consume_char('<') && self.consume_until_char('>') && self.consume_char('>')
+        if !self.consume_char('<') {
+            return false;
+        }
+        let mut consumed = false;
+        loop {
+            consumed |= self.consume_until_any("'\">");
+            match self.peek() {
+                Some('\'') | Some('"') => {
+                    if !self.consume_attribute_value() {
+                        return false;
+                    }
+                    consumed = true;
+                }
+                _ => break,
+            }
+        }
+        consumed && self.consume_char('>')
     }
 
     #[inline]
@@ -270,6 +352,17 @@ impl<'l> Reader<'l> {
         self.offset == self.content.len()
     }
 
+    #[inline]
+    pub fn locate(&self, content: &str) -> Range<usize> {
+        let start = content.as_ptr() as usize - self.content.as_ptr() as usize;
+        start..(start + content.len())
+    }
+
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
     #[inline]
     pub fn peek(&mut self) -> Option<char> {
         self.cursor.peek().copied()
@@ -371,6 +464,24 @@ mod tests {
         let content = reader.capture(|reader| reader.consume_any("cde"));
 
         assert_eq!(content.unwrap(), "cde");
+
+        let mut reader = Reader::new(" a \n");
+        let content = reader.capture_exact(|reader| reader.consume_all());
+
+        assert_eq!(content.unwrap(), " a \n");
+    }
+
+    #[test]
+    fn locate() {
+        let content = "abcdefg";
+        let mut reader = Reader::new(content);
+
+        assert!(reader.consume_any("ab"));
+        assert_eq!(reader.offset(), 2);
+
+        let value = reader.capture(|reader| reader.consume_any("cde"));
+        assert_eq!(reader.locate(value.unwrap()), 2..5);
+        assert_eq!(reader.locate(&content[6..]), 6..7);
     }
 
     #[test]
@@ -407,6 +518,31 @@ mod tests {
         test!("foo='bar &bar qux'");
     }
 
+    #[test]
+    fn consume_cdata() {
+        macro_rules! test(
+            ($content:expr, $value:expr) => ({
+                let mut reader = Reader::new($content);
+                let value = reader.capture(|reader| reader.consume_cdata());
+                assert_eq!(value.unwrap(), $value);
+            });
+        );
+
+        test!("<![CDATA[foo]]> bar", "<![CDATA[foo]]>");
+        test!("<![CDATA[a > b]] ]]> bar", "<![CDATA[a > b]] ]]>");
+        test!("<![CDATA[]]>", "<![CDATA[]]>");
+
+        macro_rules! test(
+            ($content:expr) => ({
+                let mut reader = Reader::new($content);
+                assert!(!reader.consume_cdata());
+            });
+        );
+
+        test!("<![CDATA[foo]]");
+        test!("<![CDATA foo]]>");
+    }
+
     #[test]
     fn consume_comment() {
         macro_rules! test(
@@ -431,6 +567,37 @@ mod tests {
         test!("<!-- B+, B, or B--->");
     }
 
+    #[test]
+    fn consume_declaration() {
+        macro_rules! test(
+            ($content:expr, $value:expr) => ({
+                let mut reader = Reader::new($content);
+                let value = reader.capture(|reader| reader.consume_declaration());
+                assert_eq!(value.unwrap(), $value);
+            });
+        );
+
+        test!("<!DOCTYPE svg> foo", "<!DOCTYPE svg>");
+        test!(
+            r#"<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "svg11.dtd"> foo"#,
+            r#"<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "svg11.dtd">"#
+        );
+        test!(
+            "<!DOCTYPE svg [<!ENTITY foo '>]'>]> bar",
+            "<!DOCTYPE svg [<!ENTITY foo '>]'>]>"
+        );
+
+        macro_rules! test(
+            ($content:expr) => ({
+                let mut reader = Reader::new($content);
+                assert!(!reader.consume_declaration());
+            });
+        );
+
+        test!("<!DOCTYPE svg 'foo>");
+        test!("<!DOCTYPE svg [<!ENTITY foo 'bar'>");
+    }
+
     #[test]
     fn consume_name() {
         macro_rules! test(
@@ -472,6 +639,8 @@ mod tests {
 
         test!("1 ", "1");
         test!("1a", "1");
+        test!("1em", "1");
+        test!("1.5ex", "1.5");
 
         test!("1", "1");
         test!("-1", "-1");
@@ -543,6 +712,36 @@ mod tests {
         test!("foo; bar");
     }
 
+    #[test]
+    fn consume_tag() {
+        macro_rules! test(
+            ($content:expr, $value:expr) => ({
+                let mut reader = Reader::new($content);
+                let value = reader.capture(|reader| reader.consume_tag());
+                assert_eq!(value.unwrap(), $value);
+            });
+        );
+
+        test!("<foo> bar", "<foo>");
+        test!("<foo bar='>'/> baz", "<foo bar='>'/>");
+        test!(
+            r#"<text data-expr="a > b" style="content:'>'">"#,
+            r#"<text data-expr="a > b" style="content:'>'">"#
+        );
+
+        macro_rules! test(
+            ($content:expr) => ({
+                let mut reader = Reader::new($content);
+                assert!(!reader.consume_tag());
+            });
+        );
+
+        test!("<>");
+        test!("<foo");
+        test!("<foo bar='>");
+        test!("<foo bar='<'>");
+    }
+
     #[test]
     fn consume_whitespace() {
         let mut reader = Reader::new(" \t  \n\n  \tm ");
@@ -0,0 +1,108 @@
+//! The spans.
+
+use std::ops::Range;
+
+use super::{Event, Parser};
+
+/// A span of an event.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Span<'l> {
+    /// The byte range of the event in the content.
+    pub range: Range<usize>,
+    /// The byte ranges of the attributes of a tag in the content.
+    pub attributes: Vec<(&'l str, Range<usize>)>,
+}
+
+/// A parser pairing each event with its span.
+pub struct Spanned<'l> {
+    parser: Parser<'l>,
+}
+
+impl<'l> Spanned<'l> {
+    #[inline]
+    pub(super) fn new(parser: Parser<'l>) -> Self {
+        Spanned { parser }
+    }
+}
+
+impl<'l> Iterator for Spanned<'l> {
+    type Item = (Event<'l>, Span<'l>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.parser.next()?;
+        let attributes = match event {
+            Event::Tag(..) => self
+                .parser
+                .sources
+                .iter()
+                .map(|&(name, content)| (name, self.parser.reader.locate(content)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let range = self.parser.range.clone();
+        Some((event, Span { range, attributes }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{Event, Parser};
+
+    #[test]
+    fn spanned() {
+        macro_rules! test(
+            ($parser:expr, $content:expr, $matcher:pat, $value:expr $(, $attribute:expr)*) => ({
+                let (event, span) = $parser.next().unwrap();
+                assert!(matches!(event, $matcher));
+                assert_eq!(&$content[span.range], $value);
+                let attributes = span
+                    .attributes
+                    .into_iter()
+                    .map(|(name, range)| (name, &$content[range]))
+                    .collect::<Vec<_>>();
+                let expected: &[(&str, &str)] = &[$($attribute),*];
+                assert_eq!(attributes, expected);
+            });
+        );
+
+        let content =
+            "<?xml?>\n<svg a='1'>\n  foo &amp; bar <!-- baz -->\n  <g  b = \"2\"   c='3'/>\n</svg>";
+        let mut parser = Parser::new(content).spanned();
+
+        test!(parser, content, Event::Instruction(_), "<?xml?>");
+        test!(
+            parser,
+            content,
+            Event::Tag(..),
+            "<svg a='1'>",
+            ("a", "a='1'")
+        );
+        test!(parser, content, Event::Text(_), "foo &amp; bar");
+        test!(parser, content, Event::Comment(_), "<!-- baz -->");
+        test!(
+            parser,
+            content,
+            Event::Tag(..),
+            "<g  b = \"2\"   c='3'/>",
+            ("b", "b = \"2\""),
+            ("c", "c='3'")
+        );
+        test!(parser, content, Event::Tag(..), "</svg>");
+        assert!(parser.next().is_none());
+
+        let content = "<svg>\n<g a='1'>";
+        let mut parser = Parser::new(content).well_formed(true).spanned();
+
+        test!(parser, content, Event::Tag(..), "<svg>");
+        test!(parser, content, Event::Tag(..), "<g a='1'>", ("a", "a='1'"));
+        test!(parser, content, Event::Error(_), "<g a='1'>");
+        test!(parser, content, Event::Error(_), "<svg>");
+        assert!(parser.next().is_none());
+
+        let content = "<svg><!-- foo";
+        let mut parser = Parser::new(content).spanned();
+
+        test!(parser, content, Event::Tag(..), "<svg>");
+        test!(parser, content, Event::Error(_), "<!-- foo");
+    }
+}
@@ -0,0 +1,260 @@
+//! The streams.
+
+use std::io::{BufRead, Error as IOError, ErrorKind};
+use std::mem;
+
+use super::{Error, OwnedEvent, Parser, Reader, State, Whitespace};
+
+/// A parser pulling content incrementally from a buffered source.
+///
+/// The source is read one text or markup construct at a time, and the events
+/// are the same as the ones produced by `Parser` but owned.
+pub struct Stream<T> {
+    source: T,
+    state: State,
+    position: (usize, usize),
+    done: bool,
+}
+
+impl<T> Stream<T>
+where
+    T: BufRead,
+{
+    /// Create a parser.
+    #[inline]
+    pub fn new(source: T) -> Self {
+        Stream {
+            source,
+            state: State::default(),
+            position: (1, 1),
+            done: false,
+        }
+    }
+
+    /// Check that elements are properly nested.
+    ///
+    /// See `Parser::well_formed` for details.
+    pub fn well_formed(mut self, value: bool) -> Self {
+        self.state.stack = if value { Some(Vec::new()) } else { None };
+        self
+    }
+
+    /// Set the treatment of whitespace in text.
+    pub fn whitespace(mut self, value: Whitespace) -> Self {
+        self.state.whitespace = value;
+        self
+    }
+
+    fn read(&mut self) -> std::io::Result<Option<String>> {
+        let mut buffer = Vec::new();
+        match self.source.fill_buf()?.first() {
+            Some(b'<') => {
+                let mut scanner = Scanner::default();
+                loop {
+                    if self.source.read_until(b'>', &mut buffer)? == 0 || scanner.complete(&buffer)
+                    {
+                        break;
+                    }
+                }
+            }
+            Some(_) => loop {
+                let available = self.source.fill_buf()?;
+                if available.is_empty() {
+                    break;
+                }
+                match available.iter().position(|&byte| byte == b'<') {
+                    Some(count) => {
+                        buffer.extend_from_slice(&available[..count]);
+                        self.source.consume(count);
+                        break;
+                    }
+                    _ => {
+                        let count = available.len();
+                        buffer.extend_from_slice(available);
+                        self.source.consume(count);
+                    }
+                }
+            },
+            _ => return Ok(None),
+        }
+        match String::from_utf8(buffer) {
+            Ok(content) => Ok(Some(content)),
+            Err(error) => Err(IOError::new(ErrorKind::InvalidData, error)),
+        }
+    }
+
+    fn run<F>(&mut self, content: &str, block: F) -> Option<OwnedEvent>
+    where
+        F: FnOnce(&mut Parser) -> Option<OwnedEvent>,
+    {
+        let reader = Reader::with_position(content, self.position);
+        let mut parser = Parser::resume(reader, mem::take(&mut self.state));
+        let event = block(&mut parser);
+        self.state = parser.state;
+        for c in content.chars() {
+            if c == '\n' {
+                self.position = (self.position.0 + 1, 1);
+            } else {
+                self.position.1 += 1;
+            }
+        }
+        event
+    }
+}
+
+impl<T> Iterator for Stream<T>
+where
+    T: BufRead,
+{
+    type Item = OwnedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let content = match self.read() {
+                Ok(Some(content)) => content,
+                Ok(None) => break,
+                Err(error) => {
+                    self.done = true;
+                    return Some(OwnedEvent::Error(Error::new(
+                        self.position,
+                        error.to_string(),
+                    )));
+                }
+            };
+            let event = self.run(&content, |parser| parser.advance().map(OwnedEvent::from));
+            if event.is_some() {
+                return event;
+            }
+        }
+        self.done = true;
+        self.run("", |parser| parser.finish().map(OwnedEvent::from))
+    }
+}
+
+// The state of searching for the end of markup, which is kept between reads so
+// that each byte is inspected only once.
+#[derive(Default)]
+struct Scanner {
+    offset: usize,
+    quote: Option<u8>,
+    subset: bool,
+}
+
+impl Scanner {
+    fn complete(&mut self, content: &[u8]) -> bool {
+        if content.starts_with(b"<!--") {
+            return content.len() >= 7 && content.ends_with(b"-->");
+        }
+        if content.starts_with(b"<![CDATA[") {
+            return content.len() >= 12 && content.ends_with(b"]]>");
+        }
+        if content.starts_with(b"<?") {
+            return content.len() >= 4 && content.ends_with(b"?>");
+        }
+        let declaration = content.starts_with(b"<!");
+        let start = self.offset.max(1);
+        self.offset = content.len();
+        for &byte in &content[start..] {
+            match (self.quote, byte) {
+                (Some(quote), _) if byte == quote => self.quote = None,
+                (Some(_), _) => {}
+                (_, b'\'' | b'"') => self.quote = Some(byte),
+                (_, b'[') if declaration => self.subset = true,
+                (_, b']') if declaration => self.subset = false,
+                (_, b'>') if !self.subset => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::{BufReader, Read};
+
+    use crate::parser::{Event, OwnedEvent, Parser, Stream};
+
+    #[test]
+    fn stream() {
+        let content = r#"<?xml version="1.0"?>
+            <!DOCTYPE svg [<!ENTITY foo "bar">]>
+            <svg a='>'><!-- b > c --><![CDATA[d > e]]>&foo; &#x3c;<g/>
+            <text>f</text></svg>"#;
+        let expected = Parser::new(content)
+            .map(OwnedEvent::from)
+            .map(|event| format!("{:?}", event))
+            .collect::<Vec<_>>();
+        let mut source = content.as_bytes();
+        let events = Stream::new(BufReader::with_capacity(4, &mut source))
+            .map(|event| format!("{:?}", event))
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 11);
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn stream_cdata() {
+        let data = "a > b".repeat(50_000);
+        let content = format!("<svg><![CDATA[{}]]></svg>", data);
+        let events = Stream::new(content.as_bytes()).collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 3);
+        match &events[1] {
+            OwnedEvent::CData(value) => assert_eq!(value, &data),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn stream_instruction() {
+        let content = "<?foo a>b ?><svg><?bar > ?></svg>";
+        let expected = Parser::new(content)
+            .map(OwnedEvent::from)
+            .map(|event| format!("{:?}", event))
+            .collect::<Vec<_>>();
+        let mut source = content.as_bytes();
+        let events = Stream::new(BufReader::with_capacity(4, &mut source))
+            .map(|event| format!("{:?}", event))
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0], r#"Instruction("<?foo a>b ?>")"#);
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn stream_errors() {
+        let content = "<svg>\n  <g></svg>\n<!-- foo";
+        let expected = Parser::new(content)
+            .well_formed(true)
+            .filter_map(|event| match event {
+                Event::Error(error) => Some(error.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let errors = Stream::new(content.as_bytes())
+            .well_formed(true)
+            .filter_map(|event| match event {
+                OwnedEvent::Error(error) => Some(error.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors, expected);
+    }
+
+    #[test]
+    fn stream_file() {
+        let mut content = String::new();
+        let mut file = File::open("tests/fixtures/benton.svg").unwrap();
+        file.read_to_string(&mut content).unwrap();
+        let expected = Parser::new(&content).count();
+
+        let file = File::open("tests/fixtures/benton.svg").unwrap();
+        assert_eq!(Stream::new(BufReader::new(file)).count(), expected);
+    }
+}
@@ -0,0 +1,50 @@
+//! The adapters.
+
+use std::fmt;
+use std::io;
+
+/// An adapter writing formatted content into a byte sink.
+///
+/// Since `fmt::Error` carries no information, the first failure of the sink is
+/// kept and can be retrieved via `into_inner`.
+pub struct Adapter<T> {
+    inner: T,
+    error: Option<io::Error>,
+}
+
+impl<T> Adapter<T>
+where
+    T: io::Write,
+{
+    /// Create an adapter.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        Adapter { inner, error: None }
+    }
+
+    /// Return the sink or the first failure of it.
+    pub fn into_inner(self) -> io::Result<T> {
+        match self.error {
+            Some(error) => Err(error),
+            _ => Ok(self.inner),
+        }
+    }
+}
+
+impl<T> fmt::Write for Adapter<T>
+where
+    T: io::Write,
+{
+    fn write_str(&mut self, content: &str) -> fmt::Result {
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        match self.inner.write_all(content.as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                self.error = Some(error);
+                Err(fmt::Error)
+            }
+        }
+    }
+}
@@ -0,0 +1,565 @@
+//! The writers.
+//!
+//! ## Example
+//!
+//! ```
+//! use svg::writer::{Adapter, Options, Writer};
+//!
+//! let options = Options::new().indent("  ");
+//! let mut adapter = Adapter::new(Vec::new());
+//! let mut writer = Writer::new(&mut adapter, &options);
+//! writer.start_element("svg").unwrap();
+//! writer.attribute("xmlns", "http://www.w3.org/2000/svg").unwrap();
+//! for i in 0..3 {
+//!     writer.start_element("circle").unwrap();
+//!     writer.attribute("r", &i.to_string()).unwrap();
+//!     writer.end_element().unwrap();
+//! }
+//! writer.finish().unwrap();
+//! let content = adapter.into_inner().unwrap();
+//! assert_eq!(content.iter().filter(|&&byte| byte == b'\n').count(), 4);
+//! ```
+
+use std::fmt;
+
+use crate::node::element::path::{Command, Data};
+use crate::node::element::Element;
+use crate::node::Node;
+use crate::parser::Whitespace;
+
+mod adapter;
+mod number;
+mod options;
+
+pub use self::adapter::Adapter;
+pub use self::options::{LineEnding, Options, Precision, SelfClosing};
+
+/// A writer serializing nodes according to options.
+///
+/// Besides serializing whole nodes, the writer can be driven by individual
+/// events, which allows for producing documents without building them in
+/// memory. Misplaced events, such as an attribute after the content of an
+/// element, result in `fmt::Error`.
+pub struct Writer<'l> {
+    sink: Sink<'l>,
+    options: &'l Options,
+    stack: Vec<Frame>,
+    pending: Option<Vec<(String, String)>>,
+}
+
+struct Frame {
+    name: String,
+    bare: bool,
+    bareable: bool,
+    exact: bool,
+}
+
+struct Sink<'l> {
+    inner: &'l mut dyn fmt::Write,
+    column: usize,
+}
+
+impl<'l> Writer<'l> {
+    /// Create a writer.
+    pub fn new(sink: &'l mut dyn fmt::Write, options: &'l Options) -> Self {
+        Writer {
+            sink: Sink {
+                inner: sink,
+                column: 0,
+            },
+            options,
+            stack: Vec::new(),
+            pending: None,
+        }
+    }
+
+    /// Start an element.
+    #[inline]
+    pub fn start_element(&mut self, name: &str) -> fmt::Result {
+        self.open(name, false)
+    }
+
+    /// Add an attribute to the most recently started element.
+    pub fn attribute(&mut self, name: &str, value: &str) -> fmt::Result {
+        let attributes = self.pending.as_mut().ok_or(fmt::Error)?;
+        if name == "xml:space" && self.options.whitespace == Whitespace::XmlSpace {
+            if let Some(frame) = self.stack.last_mut() {
+                frame.exact = value == "preserve";
+            }
+        }
+        let value = match (self.options.minify, name) {
+            (true, "d") => match Data::parse(value) {
+                Ok(data) => quote(&minify(&data, self.options), true),
+                _ => quote(value, true),
+            },
+            (minify, name) => quote(&number::attribute(name, value, self.options), minify),
+        };
+        let index = if self.options.sort_attributes {
+            attributes.partition_point(|(other, _)| other.as_str() <= name)
+        } else {
+            attributes.len()
+        };
+        attributes.insert(index, (name.into(), value));
+        Ok(())
+    }
+
+    /// Write text escaping it.
+    pub fn text(&mut self, content: &str) -> fmt::Result {
+        self.place(true)?;
+        fmt::Write::write_str(&mut self.sink, &crate::node::text::escape(content))
+    }
+
+    /// Write a comment.
+    pub fn comment(&mut self, content: &str) -> fmt::Result {
+        self.place(false)?;
+        fmt::write(&mut self.sink, format_args!("<!-- {} -->", content))
+    }
+
+    /// Write a node.
+    #[inline]
+    pub fn node(&mut self, node: &dyn Node) -> fmt::Result {
+        node.serialize(self)
+    }
+
+    /// End the most recently started element.
+    #[inline]
+    pub fn end_element(&mut self) -> fmt::Result {
+        self.close()
+    }
+
+    /// End all elements that are still open.
+    pub fn finish(mut self) -> fmt::Result {
+        while !self.stack.is_empty() {
+            self.close()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn element(&mut self, element: &Element, bareable: bool) -> fmt::Result {
+        self.open(element.get_name(), bareable)?;
+        for (name, value) in element.get_attributes() {
+            self.attribute(name, value)?;
+        }
+        for child in element.get_children() {
+            child.serialize(self)?;
+        }
+        self.close()
+    }
+
+    pub(crate) fn display<T>(&mut self, node: &T, bare: bool) -> fmt::Result
+    where
+        T: fmt::Display + ?Sized,
+    {
+        self.place(bare)?;
+        fmt::write(&mut self.sink, format_args!("{}", node))
+    }
+
+    fn open(&mut self, name: &str, bareable: bool) -> fmt::Result {
+        self.place(false)?;
+        fmt::write(&mut self.sink, format_args!("<{}", name))?;
+        let exact = match self.options.whitespace {
+            Whitespace::Trim => false,
+            Whitespace::Preserve => true,
+            Whitespace::XmlSpace => self.stack.last().is_some_and(|frame| frame.exact),
+        };
+        self.stack.push(Frame {
+            name: name.into(),
+            bare: false,
+            bareable,
+            exact,
+        });
+        self.pending = Some(Vec::new());
+        Ok(())
+    }
+
+    fn close(&mut self) -> fmt::Result {
+        let depth = self.stack.len();
+        let frame = self.stack.pop().ok_or(fmt::Error)?;
+        if let Some(attributes) = self.pending.take() {
+            let tail = match self.options.self_closing {
+                _ if self.options.minify => "/>".into(),
+                SelfClosing::Compact => "/>".into(),
+                SelfClosing::Spaced => " />".into(),
+                SelfClosing::Explicit => format!("></{}>", frame.name),
+            };
+            self.write_attributes(attributes, depth, tail.chars().count())?;
+            return fmt::Write::write_str(&mut self.sink, &tail);
+        }
+        if !frame.bare && !self.options.minify {
+            self.newline(depth - 1)?;
+        }
+        fmt::write(&mut self.sink, format_args!("</{}>", frame.name))
+    }
+
+    fn flush(&mut self) -> fmt::Result {
+        if let Some(attributes) = self.pending.take() {
+            self.write_attributes(attributes, self.stack.len(), 1)?;
+            fmt::Write::write_str(&mut self.sink, ">")?;
+        }
+        Ok(())
+    }
+
+    fn newline(&mut self, depth: usize) -> fmt::Result {
+        fmt::Write::write_str(&mut self.sink, self.options.line_ending.as_str())?;
+        for _ in 0..depth {
+            fmt::Write::write_str(&mut self.sink, &self.options.indent)?;
+        }
+        Ok(())
+    }
+
+    fn place(&mut self, bare: bool) -> fmt::Result {
+        self.flush()?;
+        let depth = self.stack.len();
+        let newline = match self.stack.last_mut() {
+            Some(frame) => {
+                frame.bare = frame.exact || bare && !frame.bareable;
+                !frame.bare
+            }
+            _ => !bare && self.sink.column > 0,
+        };
+        if newline && !self.options.minify {
+            self.newline(depth)?;
+        }
+        Ok(())
+    }
+
+    fn write_attributes(
+        &mut self,
+        attributes: Vec<(String, String)>,
+        depth: usize,
+        tail: usize,
+    ) -> fmt::Result {
+        let wrap = self.options.width.is_some_and(|width| {
+            let length = attributes
+                .iter()
+                .map(|(name, value)| name.chars().count() + value.chars().count() + 2)
+                .sum::<usize>();
+            !self.options.minify
+                && !attributes.is_empty()
+                && self.sink.column + length + tail > width
+        });
+        for (name, value) in attributes {
+            if wrap {
+                self.newline(depth)?;
+            } else {
+                fmt::Write::write_str(&mut self.sink, " ")?;
+            }
+            fmt::write(&mut self.sink, format_args!("{}={}", name, value))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for Sink<'_> {
+    fn write_str(&mut self, content: &str) -> fmt::Result {
+        match content.rfind('\n') {
+            Some(index) => self.column = content[(index + 1)..].chars().count(),
+            _ => self.column += content.chars().count(),
+        }
+        self.inner.write_str(content)
+    }
+}
+
+fn minify(data: &Data, options: &Options) -> String {
+    let mut result = String::new();
+    let mut last = None;
+    let mut previous = None::<String>;
+    for command in data.iter() {
+        let letter = command.letter();
+        let parameters = match command {
+            Command::Move(_, parameters)
+            | Command::Line(_, parameters)
+            | Command::HorizontalLine(_, parameters)
+            | Command::VerticalLine(_, parameters)
+            | Command::QuadraticCurve(_, parameters)
+            | Command::SmoothQuadraticCurve(_, parameters)
+            | Command::CubicCurve(_, parameters)
+            | Command::SmoothCubicCurve(_, parameters)
+            | Command::EllipticalArc(_, parameters) => &parameters[..],
+            Command::Close => &[],
+        };
+        let implicit = last == Some(letter) && !matches!(letter, 'M' | 'm' | 'z');
+        if !implicit || parameters.is_empty() {
+            result.push(letter);
+            previous = None;
+        }
+        for number in parameters {
+            let number = number::format(&number.to_string(), options.precision, true);
+            if let Some(previous) = previous {
+                let glued =
+                    number.starts_with('-') || number.starts_with('.') && previous.contains('.');
+                if !glued {
+                    result.push(' ');
+                }
+            }
+            result.push_str(&number);
+            previous = Some(number);
+        }
+        last = Some(letter);
+    }
+    result
+}
+
+fn quote(value: &str, minify: bool) -> String {
+    let value = crate::node::text::escape(value);
+    if !minify {
+        return format!(
+            r#""{}""#,
+            value.replace('"', "&quot;").replace('\'', "&apos;"),
+        );
+    }
+    let value = value.replace("&gt;", ">");
+    if value.matches('"').count() > value.matches('\'').count() {
+        format!("'{}'", value.replace('\'', "&apos;"))
+    } else {
+        format!(r#""{}""#, value.replace('"', "&quot;"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineEnding, Options, Precision, SelfClosing, Writer};
+    use crate::node::element::{Element, Group, Path, Text};
+    use crate::node::Node;
+
+    #[test]
+    fn writer_events() {
+        let mut text = Element::new("text");
+        text.append(crate::node::Text::new("bar & baz"));
+        let document = Group::new()
+            .set("id", "foo")
+            .add(Path::new().set("d", "M1,2"))
+            .add(text);
+        let options = Options::new().indent("  ");
+
+        let mut content = String::new();
+        let mut writer = Writer::new(&mut content, &options);
+        writer.start_element("g").unwrap();
+        writer.attribute("id", "foo").unwrap();
+        writer.node(&Path::new().set("d", "M1,2")).unwrap();
+        writer.start_element("text").unwrap();
+        writer.text("bar & baz").unwrap();
+        writer.end_element().unwrap();
+        assert!(writer.attribute("id", "qux").is_err());
+        writer.finish().unwrap();
+
+        assert_eq!(content, document.to_string_with(&options));
+    }
+
+    #[test]
+    fn writer_indent() {
+        let document = Group::new()
+            .set("id", "foo")
+            .add(Group::new().add(Path::new()).add(Text::new("bar")));
+        let options = Options::new().indent("  ");
+
+        assert_eq!(
+            document
+                .to_string_with(&options)
+                .lines()
+                .collect::<Vec<_>>(),
+            &[
+                r#"<g id="foo">"#,
+                "  <g>",
+                "    <path/>",
+                "    <text>",
+                "      bar",
+                "    </text>",
+                "  </g>",
+                "</g>",
+            ],
+        );
+        assert_eq!(
+            document.to_string_with(&Options::default()),
+            document.to_string(),
+        );
+    }
+
+    #[test]
+    fn writer_line_ending() {
+        let document = Group::new().add(Group::new());
+        let options = Options::new()
+            .line_ending(LineEnding::CrLf)
+            .self_closing(SelfClosing::Spaced);
+        assert_eq!(document.to_string_with(&options), "<g>\r\n<g />\r\n</g>");
+
+        let options = Options::new().self_closing(SelfClosing::Explicit);
+        assert_eq!(document.to_string_with(&options), "<g>\n<g></g>\n</g>");
+    }
+
+    #[test]
+    fn writer_minify() {
+        use crate::node::element::path::Data;
+        use crate::node::element::SVG;
+
+        let data = Data::new()
+            .move_to((10, -10))
+            .line_by((0, 50))
+            .line_by((-50.5, 0.5))
+            .move_to((1, 2))
+            .move_to((3, 4))
+            .close();
+        let document = SVG::new().add(
+            Group::new()
+                .set("title", r#"a "b" > c"#)
+                .add(Path::new().set("d", data))
+                .add(Text::new(" foo  bar ")),
+        );
+        let content = document.to_string_with(&Options::new().minify(true));
+
+        assert_eq!(
+            content,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+                r#"<g title='a "b" > c'>"#,
+                r#"<path d="M10-10l0 50-50.5.5M1 2M3 4z"/>"#,
+                "<text> foo  bar </text>",
+                "</g>",
+                "</svg>",
+            ),
+        );
+
+        let document = SVG::parse(&content).unwrap();
+        let group = &document.get_children().unwrap()[0];
+        let path = &group.get_children().unwrap()[0];
+        assert_eq!(group.get_attributes().unwrap()["title"], r#"a "b" > c"#);
+        assert_eq!(
+            Data::parse(&path.get_attributes().unwrap()["d"])
+                .unwrap()
+                .len(),
+            5,
+        );
+    }
+
+    #[test]
+    fn writer_precision() {
+        use crate::node::element::path::Data;
+
+        let document = Group::new().add(
+            Path::new()
+                .set("stroke-width", 0.1f32 + 0.2f32)
+                .set("d", Data::new().move_to((328.0725f64 + 1e-13, 0.5)))
+                .set("href", "https://example.com/v-1.23456/file_2.5.png")
+                .set("aria-label", "Version 2.12345 notes"),
+        );
+        let options = Options::new().precision(Some(Precision::Decimals(2)));
+
+        assert_eq!(
+            document.to_string_with(&options),
+            "<g>\n<path stroke-width=\"0.3\" d=\"M328.07,0.5\" \
+             href=\"https://example.com/v-1.23456/file_2.5.png\" \
+             aria-label=\"Version 2.12345 notes\"/>\n</g>",
+        );
+
+        let options = options.minify(true);
+        assert_eq!(
+            document.to_string_with(&options),
+            concat!(
+                r#"<g><path stroke-width="0.3" d="M328.07.5" "#,
+                r#"href="https://example.com/v-1.23456/file_2.5.png" "#,
+                r#"aria-label="Version 2.12345 notes"/></g>"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn writer_sort_attributes() {
+        let document = Group::new()
+            .set("id", "foo")
+            .set("class", "bar")
+            .add(Path::new().set("d", "M1,2").set("fill", "red"));
+        let options = Options::new().sort_attributes(true);
+        assert_eq!(
+            document.to_string_with(&options),
+            "<g class=\"bar\" id=\"foo\">\n<path d=\"M1,2\" fill=\"red\"/>\n</g>",
+        );
+        assert_eq!(
+            document
+                .get_attributes()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            &["id", "class"],
+        );
+
+        let mut content = String::new();
+        let mut writer = Writer::new(&mut content, &options);
+        writer.start_element("g").unwrap();
+        writer.attribute("y", "1").unwrap();
+        writer.attribute("x", "2").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(content, r#"<g x="2" y="1"/>"#);
+    }
+
+    #[test]
+    fn writer_whitespace() {
+        use crate::node::element::tag::Type;
+        use crate::parser::{Event, Parser, Whitespace};
+
+        let content = concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\">\n",
+            "  <g>\n",
+            "    <text> a <tspan>b</tspan>  c </text><path/>\n",
+            "  </g>\n",
+            "</svg>",
+        );
+        let write = |options: &Options| {
+            let mut result = String::new();
+            let mut writer = Writer::new(&mut result, options);
+            for event in Parser::new(content).whitespace(Whitespace::Preserve) {
+                match event {
+                    Event::Tag(name, kind, attributes) => {
+                        if kind != Type::End {
+                            writer.start_element(name).unwrap();
+                            for (name, value) in attributes.iter() {
+                                writer.attribute(name, value).unwrap();
+                            }
+                        }
+                        if kind != Type::Start {
+                            writer.end_element().unwrap();
+                        }
+                    }
+                    Event::Text(content) => writer.text(&content).unwrap(),
+                    _ => unreachable!(),
+                }
+            }
+            writer.finish().unwrap();
+            result
+        };
+
+        let options = Options::new().indent("  ").whitespace(Whitespace::Preserve);
+        assert_eq!(write(&options), content);
+        assert_ne!(write(&Options::new().indent("  ")), content);
+
+        let document = Group::new().set("xml:space", "preserve").add(Group::new());
+        let options = Options::new().whitespace(Whitespace::Trim);
+        assert_eq!(
+            document.to_string_with(&options),
+            "<g xml:space=\"preserve\">\n<g/>\n</g>",
+        );
+        assert_eq!(document.to_string(), r#"<g xml:space="preserve"><g/></g>"#);
+    }
+
+    #[test]
+    fn writer_width() {
+        let document = Group::new()
+            .add(Path::new().set("fill", "none").set("d", "M1,2 L3,4"))
+            .add(Path::new().set("d", "M1,2"));
+        let options = Options::new().indent("\t").width(Some(20));
+
+        assert_eq!(
+            document
+                .to_string_with(&options)
+                .lines()
+                .collect::<Vec<_>>(),
+            &[
+                "<g>",
+                "\t<path",
+                "\t\tfill=\"none\"",
+                "\t\td=\"M1,2 L3,4\"/>",
+                "\t<path d=\"M1,2\"/>",
+                "</g>",
+            ],
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! The numbers.
+
+use std::borrow::Cow;
+
+use super::{Options, Precision};
+use crate::node::element::path::Data;
+use crate::node::value::{Style, Transform};
+
+/// The attributes whose values are numbers, lengths, or lists thereof.
+const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "baseFrequency",
+    "cx",
+    "cy",
+    "dx",
+    "dy",
+    "fill-opacity",
+    "flood-opacity",
+    "font-size",
+    "fr",
+    "fx",
+    "fy",
+    "height",
+    "k1",
+    "k2",
+    "k3",
+    "k4",
+    "kernelMatrix",
+    "keySplines",
+    "keyTimes",
+    "letter-spacing",
+    "markerHeight",
+    "markerWidth",
+    "offset",
+    "opacity",
+    "order",
+    "pathLength",
+    "points",
+    "r",
+    "radius",
+    "refX",
+    "refY",
+    "rotate",
+    "rx",
+    "ry",
+    "scale",
+    "specularExponent",
+    "startOffset",
+    "stdDeviation",
+    "stop-opacity",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "surfaceScale",
+    "values",
+    "viewBox",
+    "width",
+    "word-spacing",
+    "x",
+    "x1",
+    "x2",
+    "y",
+    "y1",
+    "y2",
+    "z",
+];
+
+/// Format a number given as a token.
+pub(crate) fn format(token: &str, precision: Option<Precision>, strip: bool) -> String {
+    let mut result = match (precision, token.parse::<f64>()) {
+        (Some(precision), Ok(value)) if value.is_finite() => {
+            let decimals = match precision {
+                Precision::Decimals(decimals) => decimals,
+                Precision::Significant(_) if value == 0.0 => 0,
+                Precision::Significant(digits) => {
+                    let magnitude = value.abs().log10().floor() as isize;
+                    (digits.max(1) as isize - 1 - magnitude).max(0) as usize
+                }
+            };
+            let mut result = format!("{:.*}", decimals, value);
+            if result.contains('.') {
+                let length = result.trim_end_matches('0').trim_end_matches('.').len();
+                result.truncate(length);
+            }
+            if result == "-0" {
+                result.remove(0);
+            }
+            result
+        }
+        _ => token.to_string(),
+    };
+    if strip {
+        if result.starts_with("0.") {
+            result.remove(0);
+        } else if result.starts_with("-0.") {
+            result.remove(1);
+        }
+    }
+    result
+}
+
+/// Format the numbers found in the value of an attribute.
+///
+/// Only path data, transform lists, the attributes known to be numeric, and
+/// the declarations of such properties in styles are affected; anything else,
+/// including values that fail to parse, is left intact.
+pub(crate) fn attribute<'l>(name: &str, value: &'l str, options: &Options) -> Cow<'l, str> {
+    if options.precision.is_none() && !options.strip_leading_zeros {
+        return Cow::Borrowed(value);
+    }
+    match name {
+        "d" if Data::parse(value).is_ok() => round(value, true, options),
+        "transform" | "gradientTransform" | "patternTransform"
+            if Transform::parse(value).is_ok() =>
+        {
+            round(value, false, options)
+        }
+        "style" => style(value, options),
+        _ if NUMERIC_ATTRIBUTES.binary_search(&name).is_ok() => round(value, false, options),
+        _ => Cow::Borrowed(value),
+    }
+}
+
+fn style<'l>(value: &'l str, options: &Options) -> Cow<'l, str> {
+    let style = match Style::parse(value) {
+        Ok(style) => style,
+        _ => return Cow::Borrowed(value),
+    };
+    let mut changed = false;
+    let style = style
+        .iter()
+        .map(|(name, value)| {
+            let value = match NUMERIC_ATTRIBUTES.binary_search(&&*name.to_ascii_lowercase()) {
+                Ok(_) => round(value, false, options),
+                _ => Cow::Borrowed(value),
+            };
+            changed |= matches!(value, Cow::Owned(_));
+            (name, value.into_owned())
+        })
+        .collect::<Style>();
+    if !changed {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(style.to_string())
+}
+
+/// Format the numbers found in a value.
+///
+/// Numbers are recognized only when they are not glued to a preceding name,
+/// except for path data, where they follow command letters.
+pub(crate) fn round<'l>(content: &'l str, path: bool, options: &Options) -> Cow<'l, str> {
+    if options.precision.is_none() && !options.strip_leading_zeros {
+        return Cow::Borrowed(content);
+    }
+    let bytes = content.as_bytes();
+    let mut result = String::new();
+    let (mut copied, mut i, mut previous) = (0, 0, None::<Cow<str>>);
+    while i < bytes.len() {
+        let boundary = previous.is_some()
+            || i == 0
+            || match bytes[i - 1] {
+                b'0'..=b'9' | b'_' | b'#' | b'.' => false,
+                byte if byte.is_ascii_alphabetic() => path,
+                _ => true,
+            };
+        let end = if boundary { scan(bytes, i) } else { None };
+        match end {
+            Some(end) => {
+                let token = &content[i..end];
+                let mut current = Cow::Borrowed(token);
+                if token.contains(['.', 'e', 'E']) {
+                    let value = format(token, options.precision, options.strip_leading_zeros);
+                    result.push_str(&content[copied..i]);
+                    if previous.is_some_and(|previous| {
+                        !(value.starts_with('-')
+                            || value.starts_with('.') && previous.contains('.'))
+                    }) {
+                        result.push(' ');
+                    }
+                    result.push_str(&value);
+                    current = Cow::Owned(value);
+                    copied = end;
+                }
+                i = end;
+                previous = Some(current);
+            }
+            _ => {
+                i += 1;
+                previous = None;
+            }
+        }
+    }
+    if copied == 0 {
+        return Cow::Borrowed(content);
+    }
+    result.push_str(&content[copied..]);
+    Cow::Owned(result)
+}
+
+fn scan(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let digits = |bytes: &[u8], mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+    if matches!(bytes[i], b'+' | b'-') {
+        i += 1;
+    }
+    let start = i;
+    i = digits(bytes, i);
+    let integral = i > start;
+    if i < bytes.len() && bytes[i] == b'.' {
+        let end = digits(bytes, i + 1);
+        if integral || end > i + 1 {
+            i = end;
+        }
+    }
+    if i == start {
+        return None;
+    }
+    if i < bytes.len() && matches!(bytes[i], b'e' | b'E') {
+        let mut j = i + 1;
+        if j < bytes.len() && matches!(bytes[j], b'+' | b'-') {
+            j += 1;
+        }
+        let end = digits(bytes, j);
+        if end > j {
+            i = end;
+        }
+    }
+    Some(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attribute, format, round, NUMERIC_ATTRIBUTES};
+    use crate::writer::{Options, Precision};
+
+    #[test]
+    fn format_precision() {
+        macro_rules! test(
+            ($token:expr, $precision:expr, $strip:expr, $expected:expr) => (
+                assert_eq!(format($token, $precision, $strip), $expected);
+            );
+        );
+
+        test!("0.30000001", Some(Precision::Decimals(3)), false, "0.3");
+        test!(
+            "328.07250000000005",
+            Some(Precision::Decimals(2)),
+            false,
+            "328.07"
+        );
+        test!(
+            "328.07250000000005",
+            Some(Precision::Significant(4)),
+            false,
+            "328.1"
+        );
+        test!("12345.678", Some(Precision::Significant(2)), false, "12346");
+        test!(
+            "0.000123456",
+            Some(Precision::Significant(2)),
+            true,
+            ".00012"
+        );
+        test!("-0.0001", Some(Precision::Decimals(2)), false, "0");
+        test!("-0.5", None, true, "-.5");
+        test!("1e-7", None, false, "1e-7");
+    }
+
+    #[test]
+    fn round_attribute() {
+        let options = Options::new()
+            .precision(Some(Precision::Decimals(2)))
+            .strip_leading_zeros(true);
+
+        macro_rules! test(
+            ($content:expr, $path:expr, $expected:expr) => (
+                assert_eq!(round($content, $path, &options), $expected);
+            );
+        );
+
+        test!("0 0 10.123 20", false, "0 0 10.12 20");
+        test!("translate(0.30000001,-5.556)", false, "translate(.3,-5.56)");
+        test!("12.3456px", false, "12.35px");
+        test!(
+            "#123456 url(#a0.123) layer1.456",
+            false,
+            "#123456 url(#a0.123) layer1.456"
+        );
+        test!("M0.123,4.567L1.5.25", true, "M.12,4.57L1.5.25");
+        test!("M0.123,4.567", false, "M0.123,4.57");
+        test!("M1.5.25-0.001", true, "M1.5.25 0");
+    }
+
+    #[test]
+    fn attribute_names() {
+        assert!(NUMERIC_ATTRIBUTES.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let options = Options::new().precision(Some(Precision::Decimals(1)));
+
+        macro_rules! test(
+            ($name:expr, $value:expr, $expected:expr) => (
+                assert_eq!(attribute($name, $value, &options), $expected);
+            );
+        );
+
+        test!("x", "1.2345", "1.2");
+        test!("viewBox", "0 0 10.123 20", "0 0 10.1 20");
+        test!(
+            "transform",
+            "translate(0.3333,-5.555)",
+            "translate(0.3,-5.6)"
+        );
+        test!("transform", "foo(1.2345)", "foo(1.2345)");
+        test!("d", "M0.333,4.567", "M0.3,4.6");
+        test!("d", "M0.333 foo 1.2345", "M0.333 foo 1.2345");
+        test!(
+            "href",
+            "data:image/png;base64,iVBOR/3e5Kgg+1.2345AA",
+            "data:image/png;base64,iVBOR/3e5Kgg+1.2345AA"
+        );
+        test!(
+            "xlink:href",
+            "https://example.com/v-1.23456/file_2.5.png",
+            "https://example.com/v-1.23456/file_2.5.png"
+        );
+        test!(
+            "aria-label",
+            "Version 2.12345 notes",
+            "Version 2.12345 notes"
+        );
+        test!("data-value", "2.12345", "2.12345");
+        test!("id", "a 1.2345", "a 1.2345");
+        test!("class", "a 1.2345", "a 1.2345");
+        test!("k1", "0.12345", "0.1");
+        test!("kernelMatrix", "1.25 0 -1.25", "1.2 0 -1.2");
+        test!("keySplines", "0.333 0 0.667 1", "0.3 0 0.7 1");
+        test!("z", "2.345", "2.3");
+        test!("style", "stroke-width: 1.2345", "stroke-width:1.2");
+        test!(
+            "style",
+            "fill: url(#a0.123); Stroke-Width: 1.2345 !important; opacity: .55",
+            "fill:url(#a0.123);Stroke-Width:1.2 !important;opacity:0.6"
+        );
+        test!("style", "font-family: Foo 1.55", "font-family: Foo 1.55");
+        test!(
+            "style",
+            "stroke-width: 1.2345; fill",
+            "stroke-width: 1.2345; fill"
+        );
+
+        let options = Options::new()
+            .precision(Some(Precision::Decimals(0)))
+            .strip_leading_zeros(true);
+        assert_eq!(attribute("font-family", "Foo 1.55", &options), "Foo 1.55");
+        assert_eq!(attribute("opacity", "0.55", &options), "1");
+    }
+}
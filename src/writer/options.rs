@@ -0,0 +1,164 @@
+//! The options.
+
+use crate::parser::Whitespace;
+
+/// Options of serialization.
+///
+/// The default options reproduce the output of `fmt::Display`: every child
+/// that is not text is put on its own line without indentation.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub(crate) indent: String,
+    pub(crate) width: Option<usize>,
+    pub(crate) line_ending: LineEnding,
+    pub(crate) self_closing: SelfClosing,
+    pub(crate) minify: bool,
+    pub(crate) precision: Option<Precision>,
+    pub(crate) strip_leading_zeros: bool,
+    pub(crate) sort_attributes: bool,
+    pub(crate) whitespace: Whitespace,
+}
+
+/// A line ending.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineEnding {
+    /// `\n`.
+    #[default]
+    Lf,
+    /// `\r\n`.
+    CrLf,
+}
+
+/// A precision of numbers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Precision {
+    /// A number of digits after the decimal point.
+    Decimals(usize),
+    /// A number of significant digits.
+    ///
+    /// The integral part is never rounded.
+    Significant(usize),
+}
+
+/// A style of closing elements without children.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SelfClosing {
+    /// `<g/>`.
+    #[default]
+    Compact,
+    /// `<g />`.
+    Spaced,
+    /// `<g></g>`.
+    Explicit,
+}
+
+impl Options {
+    /// Create options.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the string repeated once per level of nesting at the beginning of
+    /// each line.
+    pub fn indent<T: Into<String>>(mut self, value: T) -> Self {
+        self.indent = value.into();
+        self
+    }
+
+    /// Set the maximum width of a line.
+    ///
+    /// Start tags exceeding the width have their attributes put on separate
+    /// lines indented one level deeper than the element itself.
+    pub fn width(mut self, value: Option<usize>) -> Self {
+        self.width = value;
+        self
+    }
+
+    /// Set the line ending.
+    pub fn line_ending(mut self, value: LineEnding) -> Self {
+        self.line_ending = value;
+        self
+    }
+
+    /// Set the style of closing elements without children.
+    pub fn self_closing(mut self, value: SelfClosing) -> Self {
+        self.self_closing = value;
+        self
+    }
+
+    /// Set the precision of numbers.
+    ///
+    /// The numbers with a fractional part or an exponent found in attribute
+    /// values and path data are rounded, and their trailing zeros are removed.
+    /// Integers and numbers that are part of names, such as `layer1.5`, are
+    /// left intact.
+    pub fn precision(mut self, value: Option<Precision>) -> Self {
+        self.precision = value;
+        self
+    }
+
+    /// Remove the leading zeros of numbers, as in `.5`.
+    pub fn strip_leading_zeros(mut self, value: bool) -> Self {
+        self.strip_leading_zeros = value;
+        self
+    }
+
+    /// Set the treatment of whitespace, which mirrors that of the parser.
+    ///
+    /// With `Whitespace::Preserve`, children are written as is without any
+    /// line breaks or indentation, so that text parsed with the same setting
+    /// is reproduced exactly. With `Whitespace::XmlSpace`, which is the
+    /// default, the same is done within elements where `xml:space` is set to
+    /// `preserve`. With `Whitespace::Trim`, `xml:space` is disregarded.
+    pub fn whitespace(mut self, value: Whitespace) -> Self {
+        self.whitespace = value;
+        self
+    }
+
+    /// Write the attributes of each element sorted by name.
+    ///
+    /// The elements themselves are left intact.
+    pub fn sort_attributes(mut self, value: bool) -> Self {
+        self.sort_attributes = value;
+        self
+    }
+
+    /// Produce compact output.
+    ///
+    /// When enabled, no whitespace is inserted between nodes, the indentation,
+    /// width, line ending, and self-closing style are ignored, attribute values
+    /// are quoted with whichever quote requires less escaping, and path data
+    /// is written in its shortest form, which implies stripping the leading
+    /// zeros of its numbers. Text is written as is.
+    pub fn minify(mut self, value: bool) -> Self {
+        self.minify = value;
+        self
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            indent: Default::default(),
+            width: Default::default(),
+            line_ending: Default::default(),
+            self_closing: Default::default(),
+            minify: Default::default(),
+            precision: Default::default(),
+            strip_leading_zeros: Default::default(),
+            sort_attributes: Default::default(),
+            whitespace: Whitespace::XmlSpace,
+        }
+    }
+}
+
+impl LineEnding {
+    #[inline]
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}